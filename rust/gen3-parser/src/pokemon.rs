@@ -245,6 +245,7 @@ pub(crate) mod test_helpers {
 
     /// Build a synthetic Pokemon data block with known values.
     /// Returns 100-byte party data (or 80-byte PC data if `party` is false).
+    #[allow(clippy::too_many_arguments)]
     pub fn build_pokemon_data(
         pid: u32,
         ot_id: u32,
@@ -475,7 +476,6 @@ mod tests {
         // hp=15, atk=31, def=0, spd=20, spa=10, spd=5
         let iv_data: u32 = 15
             | (31 << 5)
-            | (0 << 10)
             | (20 << 15)
             | (10 << 20)
             | (5 << 25);
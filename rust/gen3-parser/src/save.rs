@@ -108,10 +108,8 @@ fn parse_party_pokemon(section1: &[u8]) -> Vec<Gen3Pokemon> {
 fn parse_pc_box_pokemon(sections: &[Option<Vec<u8>>]) -> Vec<Vec<Gen3Pokemon>> {
     // Assemble continuous PC buffer from sections 5-13
     let mut pc_buffer = Vec::new();
-    for sec_id in 5..=13 {
-        if let Some(ref data) = sections[sec_id] {
-            pc_buffer.extend_from_slice(data);
-        }
+    for data in sections[5..=13].iter().flatten() {
+        pc_buffer.extend_from_slice(data);
     }
 
     if pc_buffer.is_empty() {
@@ -339,7 +337,7 @@ mod tests {
             .collect();
         let mut save = build_save(0, &[0xBB], 1, 2, &party);
         // Overwrite party count to 10
-        let section1_start = 1 * SECTION_SIZE; // section 1 is at position 1 (sequential)
+        let section1_start = SECTION_SIZE; // section 1 is at position 1 (sequential)
         write_u32_le(&mut save, section1_start + 0x0234, 10);
         let result = parse_gen3_save(&save).expect("should parse");
         assert_eq!(result.party_pokemon.len(), 6); // capped
@@ -1,7 +1,16 @@
+use pkmn_type_chart::{PokemonType, TypeChart};
 use wasm_bindgen::prelude::*;
 
 const NUM_TYPES: usize = 18;
-const MONO_TYPE_SENTINEL: u8 = 255;
+
+/// Multiplier of `atk` vs one team slot. A second type of 255 (or any other
+/// non-type index) means mono-type.
+fn slot_multiplier(chart: &TypeChart, atk: PokemonType, def1: u8, def2: u8) -> f64 {
+    match PokemonType::from_index(def1) {
+        Some(def1) => chart.matchup(atk, def1, PokemonType::from_index(def2)),
+        None => 1.0,
+    }
+}
 
 /// Analyze a team's defensive weaknesses, offensive coverage, and threat score.
 ///
@@ -22,6 +31,13 @@ const MONO_TYPE_SENTINEL: u8 = 255;
 /// Then S pairs of (type_idx, score)
 #[wasm_bindgen]
 pub fn analyze_team(team_types: &[u8], team_size: u8) -> Vec<f64> {
+    analyze_team_with_chart(team_types, team_size, &TypeChart::default())
+}
+
+/// Same as `analyze_team`, but evaluated against `chart`
+/// (e.g. an Inverse Battle or house-rule chart).
+#[wasm_bindgen]
+pub fn analyze_team_with_chart(team_types: &[u8], team_size: u8, chart: &TypeChart) -> Vec<f64> {
     let size = team_size as usize;
     let mut result: Vec<f64> = Vec::new();
 
@@ -30,20 +46,13 @@ pub fn analyze_team(team_types: &[u8], team_size: u8) -> Vec<f64> {
     let mut resist_counts = [0u32; NUM_TYPES];
     let mut immune_counts = [0u32; NUM_TYPES];
 
-    for atk in 0..NUM_TYPES {
+    for (atk, atk_type) in PokemonType::ALL.into_iter().enumerate() {
         for i in 0..size {
             let idx = i * 2;
             if idx + 1 >= team_types.len() {
                 break;
             }
-            let def1 = team_types[idx];
-            let def2_raw = team_types[idx + 1];
-            let def2: i8 = if def2_raw == MONO_TYPE_SENTINEL {
-                -1
-            } else {
-                def2_raw as i8
-            };
-            let mult = pkmn_type_chart::get_defensive_multiplier(atk as u8, def1, def2);
+            let mult = slot_multiplier(chart, atk_type, team_types[idx], team_types[idx + 1]);
             if mult == 0.0 {
                 immune_counts[atk] += 1;
             } else if mult > 1.0 {
@@ -67,20 +76,19 @@ pub fn analyze_team(team_types: &[u8], team_size: u8) -> Vec<f64> {
     }
 
     // --- Offensive coverage ---
-    // Collect unique type indices from team (excluding 255)
-    let mut team_atk_types: Vec<u8> = Vec::new();
+    // Collect unique types from team (excluding 255)
+    let mut team_atk_types: Vec<PokemonType> = Vec::new();
     for i in 0..size {
         let idx = i * 2;
         if idx + 1 >= team_types.len() {
             break;
         }
-        let t1 = team_types[idx];
-        let t2 = team_types[idx + 1];
-        if t1 < NUM_TYPES as u8 && !team_atk_types.contains(&t1) {
-            team_atk_types.push(t1);
-        }
-        if t2 != MONO_TYPE_SENTINEL && t2 < NUM_TYPES as u8 && !team_atk_types.contains(&t2) {
-            team_atk_types.push(t2);
+        for t in [team_types[idx], team_types[idx + 1]] {
+            if let Some(t) = PokemonType::from_index(t) {
+                if !team_atk_types.contains(&t) {
+                    team_atk_types.push(t);
+                }
+            }
         }
     }
 
@@ -89,7 +97,7 @@ pub fn analyze_team(team_types: &[u8], team_size: u8) -> Vec<f64> {
     for def in 0..NUM_TYPES {
         let mut is_covered = false;
         for &atk in &team_atk_types {
-            if pkmn_type_chart::get_effectiveness(atk, def as u8) > 1.0 {
+            if chart.effectiveness(atk.index(), def as u8) > 1.0 {
                 is_covered = true;
                 break;
             }
@@ -132,12 +140,7 @@ pub fn analyze_team(team_types: &[u8], team_size: u8) -> Vec<f64> {
     threat += gaps.len() as f64;
 
     // Clamp to 0-100
-    if threat < 0.0 {
-        threat = 0.0;
-    }
-    if threat > 100.0 {
-        threat = 100.0;
-    }
+    threat = threat.clamp(0.0, 100.0);
 
     // --- Suggested types (up to 3) ---
     // For each candidate type, score how many "problematic" types it resists/is immune to
@@ -154,8 +157,7 @@ pub fn analyze_team(team_types: &[u8], team_size: u8) -> Vec<f64> {
         let mut score: f64 = 0.0;
         for &prob_type in &problematic {
             // How does this candidate type defend against the problematic attacking type?
-            let mult =
-                pkmn_type_chart::get_defensive_multiplier(prob_type as u8, candidate as u8, -1);
+            let mult = chart.matchup(PokemonType::ALL[prob_type], PokemonType::ALL[candidate], None);
             if mult == 0.0 {
                 score += 3.0; // immunity
             } else if mult < 1.0 {
@@ -221,10 +223,20 @@ pub fn analyze_team(team_types: &[u8], team_size: u8) -> Vec<f64> {
 ///   best_multiplier: lowest defensive multiplier among team members
 #[wasm_bindgen]
 pub fn analyze_defensive_coverage(team_types: &[u8], team_size: u8) -> Vec<f64> {
+    analyze_defensive_coverage_with_chart(team_types, team_size, &TypeChart::default())
+}
+
+/// Same as `analyze_defensive_coverage`, but evaluated against `chart`.
+#[wasm_bindgen]
+pub fn analyze_defensive_coverage_with_chart(
+    team_types: &[u8],
+    team_size: u8,
+    chart: &TypeChart,
+) -> Vec<f64> {
     let size = team_size as usize;
     let mut result: Vec<f64> = Vec::with_capacity(NUM_TYPES * 4);
 
-    for atk in 0..NUM_TYPES {
+    for atk_type in PokemonType::ALL {
         let mut worst: f64 = 0.0;
         let mut best: f64 = f64::MAX;
         let mut any_resists = false;
@@ -240,14 +252,9 @@ pub fn analyze_defensive_coverage(team_types: &[u8], team_size: u8) -> Vec<f64>
             }
             has_members = true;
             let def1 = team_types[idx];
-            let def2_raw = team_types[idx + 1];
-            let def2: i8 = if def2_raw == MONO_TYPE_SENTINEL {
-                -1
-            } else {
-                def2_raw as i8
-            };
+            let def2 = team_types[idx + 1];
 
-            let mult = pkmn_type_chart::get_defensive_multiplier(atk as u8, def1, def2);
+            let mult = slot_multiplier(chart, atk_type, def1, def2);
             if mult > worst {
                 worst = mult;
             }
@@ -259,10 +266,7 @@ pub fn analyze_defensive_coverage(team_types: &[u8], team_size: u8) -> Vec<f64>
             }
 
             // STAB coverage: does this team member have the attacking type as one of its types?
-            if def1 == atk as u8 {
-                offensive_covered = 1.0;
-            }
-            if def2_raw != MONO_TYPE_SENTINEL && def2_raw == atk as u8 {
+            if def1 == atk_type.index() || def2 == atk_type.index() {
                 offensive_covered = 1.0;
             }
         }
@@ -748,4 +752,24 @@ mod tests {
         assert_eq!(w, 0.0);
         assert_eq!(r, 0.0);
     }
+
+    // ==================== Test 18: Inverse chart ====================
+    #[test]
+    fn test_inverse_chart_team() {
+        // Inverse Battle: Normal resists Ghost instead of being immune
+        let team = [NORMAL, NONE];
+        let result = analyze_team_with_chart(&team, 1, &TypeChart::inverse(6));
+
+        let (w, r, i) = get_defensive_triple(&result, GHOST as usize);
+        assert_eq!(i, 0.0, "no immunities in an inverse battle");
+        assert_eq!(w, 1.0, "normal is weak to ghost when inverted");
+        assert_eq!(r, 0.0);
+
+        // Fighting is resisted by Normal when inverted
+        let (_, r, _) = get_defensive_triple(&result, FIGHTING as usize);
+        assert_eq!(r, 1.0, "normal resists fighting when inverted");
+
+        let coverage = analyze_defensive_coverage_with_chart(&team, 1, &TypeChart::inverse(6));
+        assert_eq!(coverage[GHOST as usize * 4], 2.0, "team weak to ghost");
+    }
 }
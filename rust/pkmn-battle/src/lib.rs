@@ -1,3 +1,5 @@
+use pkmn_stats::{fixed_damage, variable_power, BattleStatModifiers, MoveContext};
+use pkmn_type_chart::PokemonType;
 use wasm_bindgen::prelude::*;
 
 // ---------------------------------------------------------------------------
@@ -33,12 +35,16 @@ impl Xorshift32 {
 // Helper: get defensive multiplier for a single attacking type vs dual-type
 // defender, using the pkmn-type-chart crate.
 //
-// def_type2 == 255 means mono-type (single type).
+// def_type2 == 255 (or any other non-type index) means mono-type.
 // ---------------------------------------------------------------------------
 
 fn defensive_multiplier(atk_type: u8, def_type1: u8, def_type2: u8) -> f64 {
-    let def2: i8 = if def_type2 == 255 { -1 } else { def_type2 as i8 };
-    pkmn_type_chart::get_defensive_multiplier(atk_type, def_type1, def2)
+    match (PokemonType::from_index(atk_type), PokemonType::from_index(def_type1)) {
+        (Some(atk), Some(def1)) => {
+            pkmn_type_chart::defensive_multiplier(atk, def1, PokemonType::from_index(def_type2))
+        }
+        _ => 1.0,
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -61,11 +67,13 @@ fn defensive_multiplier(atk_type: u8, def_type1: u8, def_type2: u8) -> f64 {
 ///
 /// Logic (matching JS `scoreMoveAgainstTarget`):
 /// - Status moves: return 40
-/// - No power (0): return 10
+/// - No power (0): return 10 (see `score_move_in_context` for fixed-damage
+///   and variable-power moves)
 /// - STAB: 1.5x if move_type matches either attacker type
 /// - Type effectiveness via `get_defensive_multiplier`
 /// - Score = power * stab * type_eff * (accuracy / 100)
 #[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
 pub fn score_move(
     power: u16,
     move_type: u8,
@@ -102,6 +110,63 @@ pub fn score_move(
     power as f64 * stab * type_eff * (accuracy as f64 / 100.0)
 }
 
+/// Base power that deals `damage` at `level` between equal Attack and
+/// Defense, i.e. the inverse of floor((2*level/5 + 2) * power / 50) + 2.
+fn equivalent_power(damage: u32, level: u32) -> f64 {
+    let level_factor = (2 * level / 5 + 2) as f64;
+    damage.saturating_sub(2) as f64 * 50.0 / level_factor
+}
+
+/// Score a move like `score_move`, using the battle state in `context` for
+/// moves whose power is not a fixed number.
+///
+/// Parameters are those of `score_move`, plus:
+/// - `move_name`: the move's name ("Seismic Toss", "low-kick", ...)
+/// - `context`: levels, HP, Speeds and weights (see `MoveContext`)
+///
+/// Logic:
+/// - Fixed-damage moves (Seismic Toss, Super Fang, Final Gambit, ...): the
+///   base power dealing the same damage between equal Attack and Defense at
+///   the attacker's level, times accuracy. No STAB; only immunities count.
+/// - Variable-power moves (Low Kick, Heavy Slam, Gyro Ball, Eruption,
+///   Reversal, ...): `score_move` with their power in `context`
+/// - Anything else: `score_move`
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn score_move_in_context(
+    move_name: &str,
+    power: u16,
+    move_type: u8,
+    attacker_type1: u8,
+    attacker_type2: u8,
+    defender_type1: u8,
+    defender_type2: u8,
+    accuracy: u8,
+    is_status: bool,
+    context: &MoveContext,
+) -> f64 {
+    if is_status {
+        return 40.0;
+    }
+    if let Some(damage) = fixed_damage(move_name, context) {
+        if defensive_multiplier(move_type, defender_type1, defender_type2) == 0.0 {
+            return 0.0;
+        }
+        return equivalent_power(damage, context.attacker_level) * (accuracy as f64 / 100.0);
+    }
+    let power = variable_power(move_name, context).map_or(power, |p| p.min(u16::MAX as u32) as u16);
+    score_move(
+        power,
+        move_type,
+        attacker_type1,
+        attacker_type2,
+        defender_type1,
+        defender_type2,
+        accuracy,
+        is_status,
+    )
+}
+
 /// Score how well a Pokemon matches up against an opponent.
 /// Used for switch-in decisions.
 ///
@@ -189,6 +254,7 @@ pub fn score_matchup(
 /// action_type: 0 = MOVE, 1 = SWITCH, 2 = MEGA_EVOLVE, 3 = TERASTALLIZE, 4 = DYNAMAX
 /// action_value: move index (0-3) or Pokemon index for switch
 #[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
 pub fn select_ai_action(
     move_scores: &[f64],
     num_moves: u8,
@@ -235,9 +301,9 @@ pub fn select_ai_action(
     // 3. Find best move score
     let mut best_move_score: f64 = f64::NEG_INFINITY;
     let mut best_move_index: usize = 0;
-    for i in 0..nm {
-        if move_scores[i] > best_move_score {
-            best_move_score = move_scores[i];
+    for (i, &score) in move_scores.iter().enumerate().take(nm) {
+        if score > best_move_score {
+            best_move_score = score;
             best_move_index = i;
         }
     }
@@ -322,6 +388,34 @@ pub fn determine_turn_order(
     }
 }
 
+/// `determine_turn_order` from raw Speed stats: each side's Speed is run
+/// through the pkmn-stats in-battle pipeline (stages, paralysis, Choice
+/// Scarf, Tailwind, weather abilities, Unburden, Quick Feet) first.
+///
+/// Under Trick Room the slower Pokemon moves first within a priority bracket.
+///
+/// Returns 1.0 if player 1 goes first, 0.0 if player 2 goes first.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn determine_turn_order_in_battle(
+    p1_priority: i8,
+    p2_priority: i8,
+    p1_speed: u16,
+    p2_speed: u16,
+    p1_speed_stage: i8,
+    p2_speed_stage: i8,
+    p1_modifiers: &BattleStatModifiers,
+    p2_modifiers: &BattleStatModifiers,
+    trick_room: bool,
+    seed: u32,
+) -> f64 {
+    let p1 = p1_modifiers.speed(p1_speed as u32, p1_speed_stage);
+    let p2 = p2_modifiers.speed(p2_speed as u32, p2_speed_stage);
+    let (p1, p2) = if trick_room { (p2, p1) } else { (p1, p2) };
+    let clamp = |speed: u32| speed.min(u16::MAX as u32) as u16;
+    determine_turn_order(p1_priority, p2_priority, clamp(p1), clamp(p2), seed)
+}
+
 /// Determine if AI should Terastallize.
 ///
 /// Returns 1.0 = yes, 0.0 = no.
@@ -333,6 +427,7 @@ pub fn determine_turn_order(
 /// 3. Easy: 15% chance.
 /// 4. Normal: if HP > 60%, 40% chance. Else return 0.0.
 #[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
 pub fn should_terastallize(
     ai_type1: u8,
     ai_type2: u8,
@@ -356,8 +451,15 @@ pub fn should_terastallize(
     for &opp_type in &opp_types {
         let mult = defensive_multiplier(opp_type, ai_type1, ai_type2);
         if mult > 1.0 {
-            // Check if tera type would fix this (single-type defender = tera_type)
-            let tera_mult = defensive_multiplier(opp_type, tera_type, 255);
+            // Check if tera type would fix this (Stellar keeps the original types)
+            let ai_type2_signed = PokemonType::from_index(ai_type2).map_or(-1, |t| t.index() as i8);
+            let tera_mult = pkmn_type_chart::get_tera_defensive_multiplier(
+                opp_type,
+                ai_type1,
+                ai_type2_signed,
+                tera_type as i8,
+                false,
+            );
             if tera_mult <= 1.0 {
                 return 1.0;
             }
@@ -368,10 +470,8 @@ pub fn should_terastallize(
     match difficulty {
         2 => {
             // Hard: if HP > 50%, 25% chance
-            if hp_ratio > 0.5 {
-                if rng.next_f64() < 0.25 {
-                    return 1.0;
-                }
+            if hp_ratio > 0.5 && rng.next_f64() < 0.25 {
+                return 1.0;
             }
             0.0
         }
@@ -385,10 +485,8 @@ pub fn should_terastallize(
         }
         _ => {
             // Normal: if HP > 60%, 40% chance
-            if hp_ratio > 0.6 {
-                if rng.next_f64() < 0.4 {
-                    return 1.0;
-                }
+            if hp_ratio > 0.6 && rng.next_f64() < 0.4 {
+                return 1.0;
             }
             0.0
         }
@@ -421,10 +519,8 @@ pub fn should_dynamax(
     match difficulty {
         2 => {
             // Hard: Dynamax strategically — when HP is high
-            if hp_ratio > 0.8 {
-                if rng.next_f64() < 0.6 {
-                    return 1.0;
-                }
+            if hp_ratio > 0.8 && rng.next_f64() < 0.6 {
+                return 1.0;
             }
             0.0
         }
@@ -445,10 +541,8 @@ pub fn should_dynamax(
         }
         _ => {
             // Normal: Dynamax if HP is high
-            if hp_ratio > 0.7 {
-                if rng.next_f64() < 0.5 {
-                    return 1.0;
-                }
+            if hp_ratio > 0.7 && rng.next_f64() < 0.5 {
+                return 1.0;
             }
             0.0
         }
@@ -536,6 +630,28 @@ mod tests {
         assert_eq!(score, 400.0);
     }
 
+    // score_move_in_context: level 50 Seismic Toss deals 50, like a 109 power move
+    #[test]
+    fn score_move_in_context_fixed_damage() {
+        let ctx = MoveContext::new(50, 100, 100, 150, 150, 100, 100, 1000, 1000);
+        let score = score_move_in_context("Seismic Toss", 0, 6, 0, 255, 0, 255, 100, false, &ctx);
+        assert!((score - 48.0 * 50.0 / 22.0).abs() < 1e-9);
+        // Ghost is immune to Fighting-type Seismic Toss
+        assert_eq!(score_move_in_context("Seismic Toss", 0, 6, 0, 255, 13, 255, 100, false, &ctx), 0.0);
+        // Unknown 0-power moves keep the flat score
+        assert_eq!(score_move_in_context("Counter", 0, 6, 0, 255, 0, 255, 100, false, &ctx), 10.0);
+    }
+
+    // score_move_in_context: Low Kick on a 100 kg target has 100 power
+    //    Fighting (6) vs Normal (0), Fighting attacker: 100 * 1.5 * 2.0 = 300
+    #[test]
+    fn score_move_in_context_variable_power() {
+        let ctx = MoveContext::new(50, 100, 100, 150, 150, 100, 100, 1000, 1000);
+        assert_eq!(score_move_in_context("Low Kick", 0, 6, 6, 255, 0, 255, 100, false, &ctx), 300.0);
+        assert_eq!(score_move_in_context("Tackle", 40, 0, 0, 255, 0, 255, 100, false, &ctx), 60.0);
+        assert_eq!(score_move_in_context("Low Kick", 0, 6, 6, 255, 0, 255, 100, true, &ctx), 40.0);
+    }
+
     // -----------------------------------------------------------------------
     // score_matchup tests
     // -----------------------------------------------------------------------
@@ -715,6 +831,29 @@ mod tests {
         assert_eq!(determine_turn_order(-6, 0, 300, 100, 42), 0.0);
     }
 
+    // Extra: Tailwind doubles the slower Pokemon past the faster one
+    #[test]
+    fn turn_order_in_battle_tailwind() {
+        let none = BattleStatModifiers::default();
+        let tailwind = BattleStatModifiers::new("", "", 0, false, false, true, false);
+        assert_eq!(determine_turn_order_in_battle(0, 0, 100, 150, 0, 0, &none, &none, false, 42), 0.0);
+        assert_eq!(determine_turn_order_in_battle(0, 0, 100, 150, 0, 0, &tailwind, &none, false, 42), 1.0);
+    }
+
+    // Extra: stages, paralysis and Trick Room
+    #[test]
+    fn turn_order_in_battle_stages_and_trick_room() {
+        let none = BattleStatModifiers::default();
+        let paralyzed = BattleStatModifiers::new("", "", 0, true, false, false, false);
+        // +1 Speed: 100 -> 150 beats 120
+        assert_eq!(determine_turn_order_in_battle(0, 0, 100, 120, 1, 0, &none, &none, false, 42), 1.0);
+        // Paralysis: 200 -> 100 loses to 120
+        assert_eq!(determine_turn_order_in_battle(0, 0, 200, 120, 0, 0, &paralyzed, &none, false, 42), 0.0);
+        // Trick Room: slower goes first, priority still wins
+        assert_eq!(determine_turn_order_in_battle(0, 0, 50, 120, 0, 0, &none, &none, true, 42), 1.0);
+        assert_eq!(determine_turn_order_in_battle(0, 1, 50, 120, 0, 0, &none, &none, true, 42), 0.0);
+    }
+
     // -----------------------------------------------------------------------
     // should_terastallize tests
     // -----------------------------------------------------------------------
@@ -740,6 +879,14 @@ mod tests {
         assert_eq!(result, 0.0);
     }
 
+    // Extra: Stellar tera keeps the original typing, so it never fixes a weakness
+    //     AI: Grass (4) mono, Opponent: Fire (1) mono, tera_type: Stellar (18)
+    #[test]
+    fn should_tera_stellar_no_fix() {
+        let result = should_terastallize(4, 255, 1, 255, 18, 0.3, 1, 42);
+        assert_eq!(result, 0.0);
+    }
+
     // Extra: No weakness, hard difficulty, high HP -> random chance
     #[test]
     fn should_tera_hard_difficulty_random() {
@@ -77,9 +77,9 @@ pub fn inherit_ivs(
     // Overwrite inherited stats from parents
     let mut inherited_info: Vec<u8> = Vec::with_capacity(num_inherited * 2 + 6);
 
-    for i in 0..num_inherited {
-        let stat = indices[i] as usize;
-        let from_parent: u8 = if xorshift32(&mut rng) % 2 == 0 { 1 } else { 2 };
+    for &stat_idx in indices.iter().take(num_inherited) {
+        let stat = stat_idx as usize;
+        let from_parent: u8 = if xorshift32(&mut rng).is_multiple_of(2) { 1 } else { 2 };
         let iv_val = if from_parent == 1 {
             parent1_ivs.get(stat).copied().unwrap_or(0)
         } else {
@@ -88,13 +88,13 @@ pub fn calculate_catch_probability(
     let mut shakes = [0.0_f64; 4];
     let mut num_shakes = 0u8;
 
-    for i in 0..4 {
+    for shake in shakes.iter_mut() {
         let roll = rng.next_f64_65536();
         num_shakes += 1;
         if roll < shake_probability {
-            shakes[i] = 1.0; // pass
+            *shake = 1.0; // pass
         } else {
-            shakes[i] = 0.0; // fail
+            *shake = 0.0; // fail
             break;
         }
     }
@@ -0,0 +1,349 @@
+use pkmn_type_chart::{normalize_name, PokemonType};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::gen3::GEN3;
+use crate::max_moves::MoveMode;
+use crate::request::{CalcField, CalcMove, CalcPokemon, DamageCalcRequest, DamageCalcResult};
+
+/// Most Pokemon a move can reach in a double battle: both foes and the
+/// attacker's partner.
+pub const MAX_DOUBLES_TARGETS: usize = 3;
+
+/// Which Pokemon a move hits in a double battle.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum MoveTarget {
+    /// One chosen target (Thunderbolt, Close Combat, ...).
+    #[default]
+    Single,
+    /// Both foes (Rock Slide, Heat Wave, Dazzling Gleam, ...).
+    AllAdjacentFoes,
+    /// Every other Pokemon on the field, partner included (Earthquake,
+    /// Surf, Discharge, ...).
+    AllAdjacent,
+}
+
+/// Redirection move used by a foe this turn.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum Redirection {
+    #[default]
+    None,
+    FollowMe,
+    /// Does not affect Grass types, Overcoat or Safety Goggles.
+    RagePowder,
+}
+
+/// One Pokemon the move may hit.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DoublesTarget {
+    pub pokemon: CalcPokemon,
+    /// The attacker's own partner rather than a foe.
+    pub ally: bool,
+    /// The target used Protect (or Detect, King's Shield, ...) this turn.
+    pub protect: bool,
+    pub redirection: Redirection,
+    /// The target's partner has Friend Guard.
+    pub friend_guard: bool,
+    /// Flower Gift on the target's side.
+    pub flower_gift: bool,
+}
+
+/// A damage calculation for a double battle: one attacker and up to three
+/// Pokemon it can hit.
+///
+/// Screens in `field` are on the foes' side; they do not apply to the
+/// attacker's partner. `field.helpingHand`, `battery`, `powerSpot` and
+/// `attackerFlowerGift` describe the attacker's partner.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DoublesCalcRequest {
+    pub attacker: CalcPokemon,
+    pub r#move: CalcMove,
+    #[serde(default)]
+    pub target: MoveTarget,
+    /// Index in `targets` a single-target move is aimed at.
+    #[serde(default)]
+    pub target_index: usize,
+    pub targets: Vec<DoublesTarget>,
+    #[serde(default)]
+    pub field: CalcField,
+}
+
+/// The outcome of the move for one target, in the order of `targets`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DoublesTargetResult {
+    /// The move reaches this target (after redirection).
+    pub hit: bool,
+    /// A single-target move aimed elsewhere was drawn in by Follow Me or
+    /// Rage Powder.
+    pub redirected: bool,
+    /// The target protected itself; `damage` is `None` unless a Max Move
+    /// or Z-Move hits through Protect.
+    pub protected: bool,
+    pub damage: Option<DamageCalcResult>,
+}
+
+impl DoublesCalcRequest {
+    /// Resolve targeting, redirection and Protect and compute the damage
+    /// against every target hit.
+    ///
+    /// Returns `None` for no or more than `MAX_DOUBLES_TARGETS` targets, a
+    /// single-target move aimed outside `targets`, or when a damage
+    /// calculation fails (see `DamageCalcRequest::calculate`).
+    pub fn calculate(&self) -> Option<Vec<DoublesTargetResult>> {
+        if self.targets.is_empty() || self.targets.len() > MAX_DOUBLES_TARGETS {
+            return None;
+        }
+        let (hit, redirected) = match self.target {
+            MoveTarget::Single => {
+                self.targets.get(self.target_index)?;
+                let index = self.redirected_index().unwrap_or(self.target_index);
+                let hit: Vec<bool> = (0..self.targets.len()).map(|i| i == index).collect();
+                (hit, index != self.target_index)
+            }
+            MoveTarget::AllAdjacentFoes => (self.targets.iter().map(|t| !t.ally).collect(), false),
+            MoveTarget::AllAdjacent => (vec![true; self.targets.len()], false),
+        };
+        // Protected targets still count towards the spread reduction. Gen 3
+        // only reduces moves aimed at both foes.
+        let spread = hit.iter().filter(|&&h| h).count() > 1
+            && (self.field.generation != GEN3 || self.target == MoveTarget::AllAdjacentFoes);
+
+        self.targets
+            .iter()
+            .zip(hit)
+            .map(|(target, hit)| {
+                let blocked = target.protect && self.r#move.mode == MoveMode::Normal;
+                let damage = if hit && !blocked {
+                    Some(self.request_for(target, spread).calculate()?)
+                } else {
+                    None
+                };
+                Some(DoublesTargetResult {
+                    hit,
+                    redirected: hit && redirected,
+                    protected: hit && target.protect,
+                    damage,
+                })
+            })
+            .collect()
+    }
+
+    /// The foe drawing in a single-target move with Follow Me or Rage
+    /// Powder, the first in `targets` if both do. Moves aimed at the
+    /// attacker's partner, Snipe Shot, Stalwart and Propeller Tail are not
+    /// redirected.
+    fn redirected_index(&self) -> Option<usize> {
+        if self.targets[self.target_index].ally {
+            return None;
+        }
+        let ability = normalize_name(&self.attacker.ability);
+        if normalize_name(&self.r#move.name) == "snipeshot"
+            || matches!(ability.as_str(), "stalwart" | "propellertail")
+        {
+            return None;
+        }
+        let powder_immune = self.attacker.types.contains(&PokemonType::Grass)
+            || ability == "overcoat"
+            || normalize_name(&self.attacker.item) == "safetygoggles";
+        self.targets.iter().position(|t| match t.redirection {
+            _ if t.ally => false,
+            Redirection::None => false,
+            Redirection::FollowMe => true,
+            Redirection::RagePowder => !powder_immune,
+        })
+    }
+
+    fn request_for(&self, target: &DoublesTarget, spread: bool) -> DamageCalcRequest {
+        let mut field = CalcField {
+            doubles: true,
+            spread,
+            friend_guard: target.friend_guard,
+            defender_flower_gift: target.flower_gift,
+            protect: target.protect,
+            ..self.field
+        };
+        if target.ally {
+            field.reflect = false;
+            field.light_screen = false;
+            field.aurora_veil = false;
+        }
+        DamageCalcRequest {
+            attacker: self.attacker.clone(),
+            defender: target.pokemon.clone(),
+            r#move: self.r#move.clone(),
+            field,
+        }
+    }
+}
+
+/// Calculate damage in a double battle against up to three targets.
+///
+/// `request` is `{ attacker, move, target, targetIndex, targets, field }`:
+/// - `attacker`, `move`, `field`: as in `calculate_damage_request`
+/// - `target`: `"single"` (default), `"allAdjacentFoes"` or `"allAdjacent"`
+/// - `targetIndex`: the target a single-target move is aimed at
+/// - `targets`: `[{ pokemon, ally, protect, redirection: "none" | "followMe"
+///   | "ragePowder", friendGuard, flowerGift }]`
+///
+/// Returns one `{ hit, redirected, protected, damage }` per target, where
+/// `damage` is a `calculate_damage_request` result or null if the target is
+/// not hit or protected (Max Moves and Z-Moves hit through Protect at 0.25x). Returns null if the request is malformed.
+#[wasm_bindgen]
+pub fn calculate_doubles_damage(request: JsValue) -> JsValue {
+    match serde_wasm_bindgen::from_value::<DoublesCalcRequest>(request)
+        .ok()
+        .and_then(|r| r.calculate())
+    {
+        Some(results) => serde_wasm_bindgen::to_value(&results).unwrap_or(JsValue::NULL),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::MoveCategory;
+    use crate::test_utils::{attack, mon};
+    use pkmn_stats::WEATHER_SUN;
+
+    fn foe() -> DoublesTarget {
+        DoublesTarget {
+            pokemon: mon(&[PokemonType::Normal]),
+            ..Default::default()
+        }
+    }
+
+    /// 80 power physical Normal move from a Fire type at two Normal foes.
+    fn request(target: MoveTarget) -> DoublesCalcRequest {
+        DoublesCalcRequest {
+            attacker: mon(&[PokemonType::Fire]),
+            r#move: attack(PokemonType::Normal, 80, MoveCategory::Physical),
+            target,
+            target_index: 0,
+            targets: vec![foe(), foe()],
+            field: CalcField::default(),
+        }
+    }
+
+    fn max_damage(results: &[DoublesTargetResult]) -> Vec<Option<u32>> {
+        results
+            .iter()
+            .map(|r| r.damage.as_ref().map(|d| d.max))
+            .collect()
+    }
+
+    #[test]
+    fn test_spread_reduction() {
+        let req = request(MoveTarget::Single);
+        assert_eq!(max_damage(&req.calculate().unwrap()), [Some(37), None]);
+
+        // 37 * 0.75 = 27.75 -> 28
+        let mut req = request(MoveTarget::AllAdjacentFoes);
+        assert_eq!(max_damage(&req.calculate().unwrap()), [Some(28), Some(28)]);
+
+        // One foe left: no reduction
+        req.targets.pop();
+        assert_eq!(max_damage(&req.calculate().unwrap()), [Some(37)]);
+
+        // Earthquake-style moves hit the partner too
+        let mut req = request(MoveTarget::AllAdjacent);
+        req.targets[1].ally = true;
+        assert_eq!(max_damage(&req.calculate().unwrap()), [Some(28), Some(28)]);
+        req.target = MoveTarget::AllAdjacentFoes;
+        assert_eq!(max_damage(&req.calculate().unwrap()), [Some(37), None]);
+
+        // Gen 3 halves moves aimed at both foes before the +2: 35 / 2 + 2 = 19,
+        // and does not reduce Earthquake-style moves
+        let mut req = request(MoveTarget::AllAdjacentFoes);
+        req.field.generation = GEN3;
+        assert_eq!(max_damage(&req.calculate().unwrap()), [Some(19), Some(19)]);
+        req.target = MoveTarget::AllAdjacent;
+        assert_eq!(max_damage(&req.calculate().unwrap()), [Some(37), Some(37)]);
+    }
+
+    #[test]
+    fn test_protect_keeps_spread() {
+        let mut req = request(MoveTarget::AllAdjacentFoes);
+        req.targets[0].protect = true;
+        let results = req.calculate().unwrap();
+        assert!(results[0].protected && results[0].hit);
+        assert_eq!(max_damage(&results), [None, Some(28)]);
+
+        // Max Moves hit through Protect at 0.25x
+        req.r#move.mode = MoveMode::Max;
+        assert_eq!(max_damage(&req.calculate().unwrap()), [Some(11), Some(44)]);
+    }
+
+    #[test]
+    fn test_redirection() {
+        let mut req = request(MoveTarget::Single);
+        req.targets[1].redirection = Redirection::FollowMe;
+        let results = req.calculate().unwrap();
+        assert_eq!(max_damage(&results), [None, Some(37)]);
+        assert!(results[1].redirected);
+
+        // Rage Powder does not draw in Grass types
+        req.targets[1].redirection = Redirection::RagePowder;
+        req.attacker.types = vec![PokemonType::Grass];
+        assert_eq!(max_damage(&req.calculate().unwrap()), [Some(37), None]);
+        req.attacker.types = vec![PokemonType::Fire];
+        req.attacker.ability = "Stalwart".to_string();
+        assert_eq!(max_damage(&req.calculate().unwrap()), [Some(37), None]);
+
+        // The redirected hit can still be protected against
+        req.attacker.ability.clear();
+        req.targets[1].protect = true;
+        let results = req.calculate().unwrap();
+        assert!(results[1].protected && !results[0].hit);
+    }
+
+    #[test]
+    fn test_ally_effects() {
+        let mut req = request(MoveTarget::Single);
+        req.field.helping_hand = true;
+        assert_eq!(
+            req.calculate().unwrap()[0].damage.as_ref().unwrap().power,
+            120
+        );
+        req.field.helping_hand = false;
+        req.field.power_spot = true;
+        assert_eq!(
+            req.calculate().unwrap()[0].damage.as_ref().unwrap().power,
+            104
+        );
+        // Battery only boosts special moves
+        req.field.power_spot = false;
+        req.field.battery = true;
+        assert_eq!(
+            req.calculate().unwrap()[0].damage.as_ref().unwrap().power,
+            80
+        );
+
+        req.field.battery = false;
+        req.targets[0].friend_guard = true;
+        // 37 * 0.75 = 27.75 -> 28
+        assert_eq!(max_damage(&req.calculate().unwrap())[0], Some(28));
+
+        req.targets[0].friend_guard = false;
+        req.field.weather = WEATHER_SUN;
+        req.field.attacker_flower_gift = true;
+        assert_eq!(
+            req.calculate().unwrap()[0].damage.as_ref().unwrap().attack,
+            150
+        );
+    }
+
+    #[test]
+    fn test_invalid_targets() {
+        let mut req = request(MoveTarget::Single);
+        req.target_index = 2;
+        assert!(req.calculate().is_none());
+        req.targets.clear();
+        assert!(req.calculate().is_none());
+    }
+}
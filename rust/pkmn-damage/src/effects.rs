@@ -0,0 +1,111 @@
+use pkmn_type_chart::PokemonType;
+
+/// Type-boosting held items (1.2x base power): the Gen 2 items, the Arceus
+/// plates and the incenses. Names are normalized (see `normalize_name`).
+const TYPE_BOOST_ITEMS: [(&str, PokemonType); 40] = [
+    ("silkscarf", PokemonType::Normal),
+    ("charcoal", PokemonType::Fire),
+    ("flameplate", PokemonType::Fire),
+    ("mysticwater", PokemonType::Water),
+    ("splashplate", PokemonType::Water),
+    ("seaincense", PokemonType::Water),
+    ("waveincense", PokemonType::Water),
+    ("magnet", PokemonType::Electric),
+    ("zapplate", PokemonType::Electric),
+    ("miracleseed", PokemonType::Grass),
+    ("meadowplate", PokemonType::Grass),
+    ("roseincense", PokemonType::Grass),
+    ("nevermeltice", PokemonType::Ice),
+    ("icicleplate", PokemonType::Ice),
+    ("blackbelt", PokemonType::Fighting),
+    ("fistplate", PokemonType::Fighting),
+    ("poisonbarb", PokemonType::Poison),
+    ("toxicplate", PokemonType::Poison),
+    ("softsand", PokemonType::Ground),
+    ("earthplate", PokemonType::Ground),
+    ("sharpbeak", PokemonType::Flying),
+    ("skyplate", PokemonType::Flying),
+    ("twistedspoon", PokemonType::Psychic),
+    ("mindplate", PokemonType::Psychic),
+    ("oddincense", PokemonType::Psychic),
+    ("silverpowder", PokemonType::Bug),
+    ("insectplate", PokemonType::Bug),
+    ("hardstone", PokemonType::Rock),
+    ("stoneplate", PokemonType::Rock),
+    ("rockincense", PokemonType::Rock),
+    ("spelltag", PokemonType::Ghost),
+    ("spookyplate", PokemonType::Ghost),
+    ("dragonfang", PokemonType::Dragon),
+    ("dracoplate", PokemonType::Dragon),
+    ("blackglasses", PokemonType::Dark),
+    ("dreadplate", PokemonType::Dark),
+    ("metalcoat", PokemonType::Steel),
+    ("ironplate", PokemonType::Steel),
+    ("fairyfeather", PokemonType::Fairy),
+    ("pixieplate", PokemonType::Fairy),
+];
+
+/// Berries that halve a super effective hit of their type (Chilan Berry:
+/// any Normal-type hit).
+const RESIST_BERRIES: [(&str, PokemonType); 18] = [
+    ("chilanberry", PokemonType::Normal),
+    ("occaberry", PokemonType::Fire),
+    ("passhoberry", PokemonType::Water),
+    ("wacanberry", PokemonType::Electric),
+    ("rindoberry", PokemonType::Grass),
+    ("yacheberry", PokemonType::Ice),
+    ("chopleberry", PokemonType::Fighting),
+    ("kebiaberry", PokemonType::Poison),
+    ("shucaberry", PokemonType::Ground),
+    ("cobaberry", PokemonType::Flying),
+    ("payapaberry", PokemonType::Psychic),
+    ("tangaberry", PokemonType::Bug),
+    ("chartiberry", PokemonType::Rock),
+    ("kasibberry", PokemonType::Ghost),
+    ("habanberry", PokemonType::Dragon),
+    ("colburberry", PokemonType::Dark),
+    ("babiriberry", PokemonType::Steel),
+    ("roseliberry", PokemonType::Fairy),
+];
+
+/// Type boosted by a held item, if it is a type-boosting item.
+pub(crate) fn type_boost_item(item: &str) -> Option<PokemonType> {
+    TYPE_BOOST_ITEMS
+        .iter()
+        .find(|(name, _)| *name == item)
+        .map(|&(_, t)| t)
+}
+
+/// Type resisted by a held berry, if it is a resist berry.
+pub(crate) fn resist_berry(item: &str) -> Option<PokemonType> {
+    RESIST_BERRIES
+        .iter()
+        .find(|(name, _)| *name == item)
+        .map(|&(_, t)| t)
+}
+
+/// Pinch abilities: 1.5x Attack / Sp. Atk for their type at 1/3 HP or less.
+pub(crate) fn pinch_ability_type(ability: &str) -> Option<PokemonType> {
+    match ability {
+        "blaze" => Some(PokemonType::Fire),
+        "torrent" => Some(PokemonType::Water),
+        "overgrow" => Some(PokemonType::Grass),
+        "swarm" => Some(PokemonType::Bug),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_tables() {
+        assert_eq!(type_boost_item("charcoal"), Some(PokemonType::Fire));
+        assert_eq!(type_boost_item("pixieplate"), Some(PokemonType::Fairy));
+        assert_eq!(type_boost_item("lifeorb"), None);
+        assert_eq!(resist_berry("shucaberry"), Some(PokemonType::Ground));
+        assert_eq!(resist_berry("sitrusberry"), None);
+        assert_eq!(pinch_ability_type("torrent"), Some(PokemonType::Water));
+    }
+}
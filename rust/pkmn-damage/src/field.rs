@@ -0,0 +1,213 @@
+use pkmn_stats::{
+    base_weather, WEATHER_HARSH_SUN, WEATHER_HEAVY_RAIN, WEATHER_RAIN, WEATHER_SAND, WEATHER_SNOW,
+    WEATHER_SUN,
+};
+use pkmn_type_chart::PokemonType;
+
+/// Terrain ids: 0 = none.
+pub const TERRAIN_ELECTRIC: u8 = 1;
+pub const TERRAIN_GRASSY: u8 = 2;
+pub const TERRAIN_PSYCHIC: u8 = 3;
+pub const TERRAIN_MISTY: u8 = 4;
+
+/// Weather in effect: none if any Pokemon on the field has Cloud Nine or
+/// Air Lock. `abilities` are normalized names (see `normalize_name`).
+pub fn effective_weather(weather: u8, abilities: &[&str]) -> u8 {
+    if abilities
+        .iter()
+        .any(|&a| a == "cloudnine" || a == "airlock")
+    {
+        0
+    } else {
+        weather
+    }
+}
+
+/// Weather multiplier on a move's damage: 1.5 for Fire in sun and Water in
+/// rain, 0.5 the other way round. Returns 0 for a move that fails outright
+/// (Water in harsh sunlight, Fire in heavy rain).
+pub fn weather_modifier(weather: u8, move_type: PokemonType) -> f64 {
+    match (weather, move_type) {
+        (WEATHER_HARSH_SUN, PokemonType::Water) | (WEATHER_HEAVY_RAIN, PokemonType::Fire) => 0.0,
+        _ => match (base_weather(weather), move_type) {
+            (WEATHER_SUN, PokemonType::Fire) | (WEATHER_RAIN, PokemonType::Water) => 1.5,
+            (WEATHER_SUN, PokemonType::Water) | (WEATHER_RAIN, PokemonType::Fire) => 0.5,
+            _ => 1.0,
+        },
+    }
+}
+
+/// Weather boost to the defending stat: sandstorm raises Rock types'
+/// Sp. Def and snow raises Ice types' Defense by 1.5x.
+pub fn weather_defense_modifier(
+    weather: u8,
+    defender_types: &[PokemonType],
+    targets_defense: bool,
+) -> f64 {
+    let boosted = match weather {
+        WEATHER_SAND => !targets_defense && defender_types.contains(&PokemonType::Rock),
+        WEATHER_SNOW => targets_defense && defender_types.contains(&PokemonType::Ice),
+        _ => false,
+    };
+    if boosted {
+        1.5
+    } else {
+        1.0
+    }
+}
+
+/// Whether a Pokemon is affected by terrain and Ground-type moves: not a
+/// Flying type, no Levitate or Air Balloon, unless Gravity or an Iron Ball
+/// grounds it. `ability` and `item` are normalized names.
+pub fn is_grounded(types: &[PokemonType], ability: &str, item: &str, gravity: bool) -> bool {
+    if gravity || item == "ironball" {
+        return true;
+    }
+    !types.contains(&PokemonType::Flying) && ability != "levitate" && item != "airballoon"
+}
+
+/// Terrain multiplier on a move's base power.
+///
+/// - Electric / Grassy / Psychic Terrain: 1.3x to moves of that type from a
+///   grounded attacker
+/// - Misty Terrain: 0.5x to Dragon moves against a grounded target
+/// - Grassy Terrain: 0.5x to Earthquake, Bulldoze and Magnitude against a
+///   grounded target
+///
+/// `move_name` is a normalized name.
+pub fn terrain_modifier(
+    terrain: u8,
+    move_type: PokemonType,
+    move_name: &str,
+    attacker_grounded: bool,
+    defender_grounded: bool,
+) -> f64 {
+    let boosted_type = match terrain {
+        TERRAIN_ELECTRIC => Some(PokemonType::Electric),
+        TERRAIN_GRASSY => Some(PokemonType::Grass),
+        TERRAIN_PSYCHIC => Some(PokemonType::Psychic),
+        _ => None,
+    };
+    if attacker_grounded && boosted_type == Some(move_type) {
+        return 5325.0 / 4096.0;
+    }
+    let weakened = match terrain {
+        TERRAIN_MISTY => move_type == PokemonType::Dragon,
+        TERRAIN_GRASSY => matches!(move_name, "earthquake" | "bulldoze" | "magnitude"),
+        _ => false,
+    };
+    if defender_grounded && weakened {
+        0.5
+    } else {
+        1.0
+    }
+}
+
+/// Reflect (physical), Light Screen (special) and Aurora Veil (both) on the
+/// defender's side: 0.5x in singles, 2732/4096 in doubles. Screens go by the
+/// move's category, so Light Screen (not Reflect) weakens Psyshock. Critical
+/// hits and Infiltrator bypass screens; pass `false` for every screen then.
+pub fn screen_modifier(
+    physical: bool,
+    reflect: bool,
+    light_screen: bool,
+    aurora_veil: bool,
+    doubles: bool,
+) -> f64 {
+    let screened = aurora_veil
+        || if physical {
+            reflect
+        } else {
+            light_screen
+        };
+    match (screened, doubles) {
+        (false, _) => 1.0,
+        (true, false) => 0.5,
+        (true, true) => 2732.0 / 4096.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pkmn_stats::WEATHER_HAIL;
+
+    #[test]
+    fn test_weather() {
+        assert_eq!(weather_modifier(WEATHER_SUN, PokemonType::Fire), 1.5);
+        assert_eq!(weather_modifier(WEATHER_RAIN, PokemonType::Fire), 0.5);
+        assert_eq!(weather_modifier(WEATHER_HARSH_SUN, PokemonType::Fire), 1.5);
+        assert_eq!(weather_modifier(WEATHER_HARSH_SUN, PokemonType::Water), 0.0);
+        assert_eq!(weather_modifier(WEATHER_HEAVY_RAIN, PokemonType::Fire), 0.0);
+        assert_eq!(
+            weather_modifier(WEATHER_HEAVY_RAIN, PokemonType::Water),
+            1.5
+        );
+        assert_eq!(weather_modifier(WEATHER_SAND, PokemonType::Rock), 1.0);
+        assert_eq!(effective_weather(WEATHER_SUN, &["blaze", "cloudnine"]), 0);
+        assert_eq!(effective_weather(WEATHER_SUN, &["blaze"]), WEATHER_SUN);
+    }
+
+    #[test]
+    fn test_weather_defense() {
+        let rock = [PokemonType::Rock, PokemonType::Ground];
+        assert_eq!(weather_defense_modifier(WEATHER_SAND, &rock, false), 1.5);
+        assert_eq!(weather_defense_modifier(WEATHER_SAND, &rock, true), 1.0);
+        let ice = [PokemonType::Ice];
+        assert_eq!(weather_defense_modifier(WEATHER_SNOW, &ice, true), 1.5);
+        // Hail has no Defense boost
+        assert_eq!(weather_defense_modifier(WEATHER_HAIL, &ice, true), 1.0);
+    }
+
+    #[test]
+    fn test_terrain() {
+        let boost = 5325.0 / 4096.0;
+        assert_eq!(
+            terrain_modifier(TERRAIN_ELECTRIC, PokemonType::Electric, "", true, true),
+            boost
+        );
+        assert_eq!(
+            terrain_modifier(TERRAIN_ELECTRIC, PokemonType::Electric, "", false, true),
+            1.0
+        );
+        assert_eq!(
+            terrain_modifier(TERRAIN_MISTY, PokemonType::Dragon, "", false, true),
+            0.5
+        );
+        assert_eq!(
+            terrain_modifier(TERRAIN_MISTY, PokemonType::Dragon, "", true, false),
+            1.0
+        );
+        assert_eq!(
+            terrain_modifier(
+                TERRAIN_GRASSY,
+                PokemonType::Ground,
+                "earthquake",
+                true,
+                true
+            ),
+            0.5
+        );
+        assert_eq!(
+            terrain_modifier(TERRAIN_PSYCHIC, PokemonType::Psychic, "", true, false),
+            boost
+        );
+
+        assert!(is_grounded(&[PokemonType::Fire], "", "", false));
+        assert!(!is_grounded(&[PokemonType::Flying], "", "", false));
+        assert!(!is_grounded(&[PokemonType::Ghost], "levitate", "", false));
+        assert!(is_grounded(&[PokemonType::Flying], "", "ironball", false));
+        assert!(is_grounded(&[PokemonType::Ghost], "levitate", "", true));
+    }
+
+    #[test]
+    fn test_screens() {
+        assert_eq!(screen_modifier(true, true, false, false, false), 0.5);
+        assert_eq!(screen_modifier(false, true, false, false, false), 1.0);
+        assert_eq!(
+            screen_modifier(false, false, true, false, true),
+            2732.0 / 4096.0
+        );
+        assert_eq!(screen_modifier(true, false, false, true, false), 0.5);
+    }
+}
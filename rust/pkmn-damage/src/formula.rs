@@ -0,0 +1,277 @@
+use wasm_bindgen::prelude::*;
+
+/// Number of damage rolls: the random factor runs from 85% to 100%.
+pub const NUM_ROLLS: usize = 16;
+
+/// Multipliers applied to the base damage, in the order the games apply them.
+///
+/// Every multiplier is applied as a 4096-based fixed-point modifier with
+/// "round half down", as in the games; use `chain_modifiers` to combine
+/// several "other" modifiers (Life Orb, Expert Belt, screens, ...) first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DamageModifiers {
+    /// The move hits more than one target (0.75x).
+    pub spread: bool,
+    /// Second hit of Parental Bond (0.25x).
+    pub parental_bond_hit: bool,
+    /// Weather multiplier (1.5 boosted, 0.5 weakened, 1.0 otherwise).
+    pub weather: f64,
+    /// The target used Glaive Rush last turn (2x).
+    pub glaive_rush: bool,
+    /// Critical hit (1.5x).
+    pub critical: bool,
+    /// STAB multiplier (1.0, 1.5, 2.0 or 2.25).
+    pub stab: f64,
+    /// Type effectiveness. 0 makes every roll 0.
+    pub effectiveness: f64,
+    /// Burned attacker using a physical move without Guts or Facade (0.5x).
+    pub burned: bool,
+    /// All remaining modifiers chained together (see `chain_modifiers`).
+    pub other: f64,
+    /// A Z-Move or Max Move that hits through Protect (0.25x).
+    pub z_move_into_protect: bool,
+}
+
+impl Default for DamageModifiers {
+    fn default() -> Self {
+        DamageModifiers {
+            spread: false,
+            parental_bond_hit: false,
+            weather: 1.0,
+            glaive_rush: false,
+            critical: false,
+            stab: 1.0,
+            effectiveness: 1.0,
+            burned: false,
+            other: 1.0,
+            z_move_into_protect: false,
+        }
+    }
+}
+
+/// Convert a multiplier to the games' 4096-based fixed-point form.
+fn to_4096(multiplier: f64) -> u64 {
+    (multiplier * 4096.0).round() as u64
+}
+
+/// Apply a multiplier, rounding half down.
+pub(crate) fn modify(value: u64, multiplier: f64) -> u64 {
+    (value * to_4096(multiplier) + 2047) / 4096
+}
+
+/// Chain several modifiers into one, rounding to 1/4096 after each step
+/// like the games do. Returns 1.0 for an empty list.
+pub fn chain_modifiers(modifiers: &[f64]) -> f64 {
+    let chained = modifiers
+        .iter()
+        .fold(4096u64, |acc, &m| (acc * to_4096(m) + 2048) >> 12);
+    chained as f64 / 4096.0
+}
+
+/// Damage before any modifiers: floor(floor(floor(2 * level / 5 + 2) * power * atk / def) / 50) + 2.
+/// A defense of 0 is treated as 1.
+pub fn base_damage(level: u32, power: u32, attack: u32, defense: u32) -> u32 {
+    let level_factor = 2 * level as u64 / 5 + 2;
+    let scaled = level_factor * power as u64 * attack as u64 / defense.max(1) as u64;
+    (scaled / 50 + 2) as u32
+}
+
+/// All 16 damage rolls, lowest (85%) to highest (100%).
+///
+/// Order: spread, Parental Bond, weather, Glaive Rush, critical hit, random
+/// factor, STAB, type effectiveness, burn, other modifiers, Z/Max Move
+/// through Protect. A hit that is not immune always deals at least 1 damage.
+/// A move with 0 power deals no damage.
+pub fn damage_rolls(
+    level: u32,
+    power: u32,
+    attack: u32,
+    defense: u32,
+    mods: &DamageModifiers,
+) -> [u32; NUM_ROLLS] {
+    if power == 0 || mods.effectiveness <= 0.0 {
+        return [0; NUM_ROLLS];
+    }
+
+    let mut base = base_damage(level, power, attack, defense) as u64;
+    if mods.spread {
+        base = modify(base, 0.75);
+    }
+    if mods.parental_bond_hit {
+        base = modify(base, 0.25);
+    }
+    base = modify(base, mods.weather);
+    if mods.glaive_rush {
+        base *= 2;
+    }
+    if mods.critical {
+        base = base * 3 / 2;
+    }
+
+    std::array::from_fn(|i| {
+        let mut damage = base * (85 + i as u64) / 100;
+        damage = modify(damage, mods.stab);
+        damage = (damage as f64 * mods.effectiveness) as u64;
+        if mods.burned {
+            damage = modify(damage, 0.5);
+        }
+        damage = modify(damage, mods.other);
+        if mods.z_move_into_protect {
+            damage = modify(damage, 0.25);
+        }
+        damage.clamp(1, u32::MAX as u64) as u32
+    })
+}
+
+/// All 16 damage rolls with the full damage formula at any level.
+///
+/// Parameters:
+/// - `level`: the attacker's level
+/// - `move_power`: base power after power modifiers (Technician, type-boosting items, ...)
+/// - `attack`, `defense`: final attacking and defending stats (stages and stat modifiers applied)
+/// - `is_spread`: the move hits multiple targets (0.75x)
+/// - `parental_bond_hit`: this is Parental Bond's second hit (0.25x)
+/// - `weather_mult`: weather multiplier (1.5, 0.5 or 1.0)
+/// - `glaive_rush`: the target used Glaive Rush (2x)
+/// - `is_critical`: critical hit (1.5x)
+/// - `stab`: STAB multiplier
+/// - `effectiveness`: type effectiveness
+/// - `is_burned`: burned attacker using a physical move without Guts or Facade
+/// - `other_modifiers`: remaining multipliers (Life Orb, Expert Belt, screens, ...), chained in order
+/// - `z_move_into_protect`: a Z-Move or Max Move hitting through Protect (0.25x)
+///
+/// Returns a `Vec<f64>` of 16 rolls from lowest to highest.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_damage_rolls(
+    level: u8,
+    move_power: u16,
+    attack: u16,
+    defense: u16,
+    is_spread: bool,
+    parental_bond_hit: bool,
+    weather_mult: f64,
+    glaive_rush: bool,
+    is_critical: bool,
+    stab: f64,
+    effectiveness: f64,
+    is_burned: bool,
+    other_modifiers: &[f64],
+    z_move_into_protect: bool,
+) -> Vec<f64> {
+    let mods = DamageModifiers {
+        spread: is_spread,
+        parental_bond_hit,
+        weather: weather_mult,
+        glaive_rush,
+        critical: is_critical,
+        stab,
+        effectiveness,
+        burned: is_burned,
+        other: chain_modifiers(other_modifiers),
+        z_move_into_protect,
+    };
+    damage_rolls(level as u32, move_power as u32, attack as u32, defense as u32, &mods)
+        .iter()
+        .map(|&d| d as f64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_damage_levels() {
+        // Level 50 uses the old 22 level factor; level 100 uses 42
+        assert_eq!(base_damage(50, 80, 100, 100), 37);
+        assert_eq!(base_damage(100, 80, 100, 100), 69);
+        assert_eq!(base_damage(5, 40, 12, 11), 5);
+        assert_eq!(base_damage(50, 80, 100, 0), base_damage(50, 80, 100, 1));
+    }
+
+    #[test]
+    fn test_glaceon_ice_fang_example() {
+        // Level 75 Glaceon (123 Atk) Ice Fang vs Garchomp (163 Def): STAB, 4x
+        let mods = DamageModifiers {
+            stab: 1.5,
+            effectiveness: 4.0,
+            ..Default::default()
+        };
+        let rolls = damage_rolls(75, 65, 123, 163, &mods);
+        assert_eq!(rolls[0], 168);
+        assert_eq!(rolls[15], 196);
+        assert!(rolls.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn test_modifier_order() {
+        let plain = damage_rolls(50, 80, 100, 100, &DamageModifiers::default());
+        assert_eq!(plain[15], 37);
+        assert_eq!(plain[0], 31);
+
+        // Spread then crit: 37 -> 28 (27.75 rounds up) -> 42
+        let spread_crit = DamageModifiers {
+            spread: true,
+            critical: true,
+            ..Default::default()
+        };
+        assert_eq!(damage_rolls(50, 80, 100, 100, &spread_crit)[15], 42);
+
+        // Parental Bond second hit: 37 * 0.25 = 9.25 -> 9
+        let bond = DamageModifiers {
+            parental_bond_hit: true,
+            ..Default::default()
+        };
+        assert_eq!(damage_rolls(50, 80, 100, 100, &bond)[15], 9);
+
+        let glaive = DamageModifiers {
+            glaive_rush: true,
+            ..Default::default()
+        };
+        assert_eq!(damage_rolls(50, 80, 100, 100, &glaive)[15], 74);
+    }
+
+    #[test]
+    fn test_other_modifiers_and_protect() {
+        // Life Orb (5324/4096)
+        let life_orb = DamageModifiers {
+            other: chain_modifiers(&[5324.0 / 4096.0]),
+            ..Default::default()
+        };
+        assert_eq!(damage_rolls(50, 80, 100, 100, &life_orb)[15], 48);
+
+        let max_move = DamageModifiers {
+            z_move_into_protect: true,
+            ..Default::default()
+        };
+        assert_eq!(damage_rolls(50, 80, 100, 100, &max_move)[15], 9);
+        assert_eq!(chain_modifiers(&[]), 1.0);
+        assert_eq!(chain_modifiers(&[1.5, 2.0]), 3.0);
+    }
+
+    #[test]
+    fn test_minimum_and_immune() {
+        let weak = DamageModifiers {
+            effectiveness: 0.25,
+            burned: true,
+            ..Default::default()
+        };
+        assert!(damage_rolls(1, 10, 5, 500, &weak).iter().all(|&d| d == 1));
+        let immune = DamageModifiers {
+            effectiveness: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(damage_rolls(50, 80, 100, 100, &immune), [0; NUM_ROLLS]);
+        assert_eq!(calculate_damage_rolls(50, 0, 100, 100, false, false, 1.0, false, false, 1.0, 1.0, false, &[], false), vec![0.0; NUM_ROLLS]);
+    }
+
+    #[test]
+    fn test_wasm_rolls() {
+        let rolls = calculate_damage_rolls(
+            100, 80, 100, 100, false, false, 1.5, false, false, 1.5, 2.0, false, &[1.3], false,
+        );
+        assert_eq!(rolls.len(), NUM_ROLLS);
+        assert!(rolls[0] < rolls[15]);
+    }
+}
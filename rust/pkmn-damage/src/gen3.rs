@@ -0,0 +1,447 @@
+use pkmn_stats::{
+    apply_stat_stage, base_weather, critical_hit_stage, fixed_damage, variable_power, MoveContext,
+    WEATHER_RAIN, WEATHER_SUN,
+};
+use pkmn_type_chart::{normalize_name, ImmunityReason, PokemonType, TypeChart};
+
+use crate::effects::{pinch_ability_type, type_boost_item};
+use crate::field::effective_weather;
+use crate::formula::NUM_ROLLS;
+use crate::request::{DamageCalcRequest, DamageCalcResult, Status};
+
+/// Generation id that selects Gen 3 mechanics (see `CalcField::generation`).
+pub const GEN3: u8 = 3;
+
+/// Before Gen 4, a move's category comes from its type: Normal, Fighting,
+/// Flying, Poison, Ground, Rock, Bug, Ghost and Steel moves are physical.
+pub fn is_gen3_physical_type(move_type: PokemonType) -> bool {
+    matches!(
+        move_type,
+        PokemonType::Normal
+            | PokemonType::Fighting
+            | PokemonType::Flying
+            | PokemonType::Poison
+            | PokemonType::Ground
+            | PokemonType::Rock
+            | PokemonType::Bug
+            | PokemonType::Ghost
+            | PokemonType::Steel
+    )
+}
+
+/// Chance of a critical hit in Gen 3: 1/16, 1/8, 1/4, 1/3, then 1/2.
+pub fn gen3_critical_hit_chance(stage: u8) -> f64 {
+    match stage {
+        0 => 1.0 / 16.0,
+        1 => 1.0 / 8.0,
+        2 => 1.0 / 4.0,
+        3 => 1.0 / 3.0,
+        _ => 0.5,
+    }
+}
+
+/// Modifiers of the Gen 3 damage formula, in the order the games apply them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gen3Modifiers {
+    /// Burned attacker using a physical move without Guts (0.5x).
+    pub burned: bool,
+    /// Reflect or Light Screen against a non-critical hit: 0.5x, 2/3 in
+    /// doubles.
+    pub screen: bool,
+    pub doubles: bool,
+    /// The move hits both foes (0.5x). Unlike later generations, moves that
+    /// also hit the partner (Earthquake, Surf) are not reduced.
+    pub spread: bool,
+    /// Weather multiplier (1.5 boosted, 0.5 weakened, 1.0 otherwise).
+    pub weather: f64,
+    /// Critical hit (2x).
+    pub critical: bool,
+    /// The attacker's partner used Helping Hand (1.5x).
+    pub helping_hand: bool,
+    /// Same-type attack bonus (1.5x).
+    pub stab: bool,
+    /// Effectiveness against each of the defender's types, applied one at a
+    /// time (1.0 for the missing second type).
+    pub effectiveness: [f64; 2],
+}
+
+impl Default for Gen3Modifiers {
+    fn default() -> Self {
+        Gen3Modifiers {
+            burned: false,
+            screen: false,
+            doubles: false,
+            spread: false,
+            weather: 1.0,
+            critical: false,
+            helping_hand: false,
+            stab: false,
+            effectiveness: [1.0, 1.0],
+        }
+    }
+}
+
+/// All 16 damage rolls with the Gen 3 formula.
+///
+/// `attack` and `defense` are final stats (stages and stat modifiers
+/// applied). Unlike later generations, every step floors on its own, the
+/// critical hit, Helping Hand and STAB are applied after the +2 and the
+/// random factor is applied last.
+pub fn gen3_damage_rolls(
+    level: u32,
+    power: u32,
+    attack: u32,
+    defense: u32,
+    mods: &Gen3Modifiers,
+) -> [u32; NUM_ROLLS] {
+    if power == 0 || mods.effectiveness.iter().any(|&e| e <= 0.0) {
+        return [0; NUM_ROLLS];
+    }
+
+    let level_factor = (2 * level as u64) / 5 + 2;
+    let mut damage = attack as u64 * power as u64 * level_factor / defense.max(1) as u64 / 50;
+    if mods.burned {
+        damage /= 2;
+    }
+    if mods.screen {
+        damage = if mods.doubles {
+            damage / 3 * 2
+        } else {
+            damage / 2
+        };
+    }
+    if mods.spread {
+        damage /= 2;
+    }
+    damage = (damage as f64 * mods.weather) as u64;
+    damage = damage.max(1) + 2;
+
+    if mods.critical {
+        damage *= 2;
+    }
+    if mods.helping_hand {
+        damage = damage * 15 / 10;
+    }
+    if mods.stab {
+        damage = damage * 15 / 10;
+    }
+    for effectiveness in mods.effectiveness {
+        damage = (damage as f64 * effectiveness) as u64;
+    }
+
+    std::array::from_fn(|i| (damage * (85 + i as u64) / 100).clamp(1, u32::MAX as u64) as u32)
+}
+
+impl DamageCalcRequest {
+    /// `calculate` with Gen 3 rules: physical/special by type, 2x critical
+    /// hits, the Gen 3 type chart (no Fairy), stat items and abilities that
+    /// modify the raw stat before stages, and the Gen 3 modifier order.
+    ///
+    /// Fixed-damage and variable-power moves are resolved as in `calculate`
+    /// (see `fixed_damage` and `variable_power`). Abilities, items and move
+    /// effects introduced later are ignored, as is the move's `mode` and
+    /// `category`.
+    pub(crate) fn calculate_gen3(&self) -> Option<DamageCalcResult> {
+        let (attacker, defender, mv) = (&self.attacker, &self.defender, &self.r#move);
+        let atk_stats = attacker.final_stats()?;
+        let def_stats = defender.final_stats()?;
+        if attacker.types.is_empty() || defender.types.is_empty() {
+            return None;
+        }
+
+        let atk_ability = normalize_name(&attacker.ability);
+        let atk_item = normalize_name(&attacker.item);
+        let def_ability = normalize_name(&defender.ability);
+        let move_name = normalize_name(&mv.name);
+        let move_type = mv.move_type;
+        let physical = is_gen3_physical_type(move_type);
+        let critical = mv.critical;
+        let attacker_hp = attacker.hp(atk_stats.hp);
+        let weather = effective_weather(
+            self.field.weather,
+            &[&atk_ability, &normalize_name(&defender.ability)],
+        );
+        let move_ctx = MoveContext {
+            attacker_level: attacker.level,
+            attacker_hp,
+            attacker_max_hp: atk_stats.hp,
+            defender_hp: defender.hp(def_stats.hp),
+            defender_max_hp: def_stats.hp,
+            attacker_speed: attacker.battle_speed(atk_stats.speed, weather),
+            defender_speed: defender.battle_speed(def_stats.speed, weather),
+            attacker_weight: attacker.weight,
+            defender_weight: defender.weight,
+        };
+
+        // Type effectiveness, one defending type at a time
+        let chart = TypeChart::for_generation(GEN3);
+        let mut effectiveness = [1.0; 2];
+        for (slot, def_type) in defender.types.iter().take(2).enumerate() {
+            effectiveness[slot] = chart.effectiveness(move_type.index(), def_type.index());
+        }
+        let multiplier = effectiveness[0] * effectiveness[1];
+        let immunity = match (def_ability.as_str(), move_type) {
+            _ if multiplier == 0.0 => ImmunityReason::Type,
+            ("levitate", PokemonType::Ground) => ImmunityReason::Levitate,
+            ("flashfire", PokemonType::Fire) => ImmunityReason::FlashFire,
+            ("waterabsorb", PokemonType::Water) => ImmunityReason::WaterAbsorb,
+            ("voltabsorb", PokemonType::Electric) => ImmunityReason::VoltAbsorb,
+            ("wonderguard", _) if multiplier <= 1.0 => ImmunityReason::WonderGuard,
+            _ => ImmunityReason::None,
+        };
+        if immunity != ImmunityReason::None {
+            effectiveness = [0.0, 0.0];
+        }
+
+        // Base power
+        let mut power = variable_power(&move_name, &move_ctx).unwrap_or(mv.power);
+        if move_name == "facade"
+            && attacker.status.is_statused()
+            && attacker.status != Status::Sleep
+        {
+            power *= 2;
+        }
+        if pinch_ability_type(&atk_ability) == Some(move_type) && attacker_hp * 3 <= atk_stats.hp {
+            power = power * 150 / 100;
+        }
+
+        // Attacking stat: modifiers on the raw stat, then the stage
+        let (mut attack, atk_stage) = if physical {
+            (atk_stats.attack, attacker.boosts.attack)
+        } else {
+            (atk_stats.sp_atk, attacker.boosts.sp_atk)
+        };
+        if physical && matches!(atk_ability.as_str(), "hugepower" | "purepower") {
+            attack *= 2;
+        }
+        if type_boost_item(&atk_item) == Some(move_type) {
+            attack = attack * 110 / 100;
+        }
+        if physical && atk_item == "choiceband" {
+            attack = attack * 150 / 100;
+        }
+        if def_ability == "thickfat" && matches!(move_type, PokemonType::Fire | PokemonType::Ice) {
+            attack /= 2;
+        }
+        if physical && atk_ability == "hustle" {
+            attack = attack * 150 / 100;
+        }
+        if physical && atk_ability == "guts" && attacker.status.is_statused() {
+            attack = attack * 150 / 100;
+        }
+        let atk_stage = if critical {
+            critical_hit_stage(atk_stage, true)
+        } else {
+            atk_stage
+        };
+        let attack = apply_stat_stage(attack, atk_stage);
+
+        // Defending stat
+        let (mut defense, def_stage) = if physical {
+            (def_stats.defense, defender.boosts.defense)
+        } else {
+            (def_stats.sp_def, defender.boosts.sp_def)
+        };
+        if physical && def_ability == "marvelscale" && defender.status.is_statused() {
+            defense = defense * 150 / 100;
+        }
+        if matches!(move_name.as_str(), "explosion" | "selfdestruct") {
+            defense /= 2;
+        }
+        let def_stage = if critical {
+            critical_hit_stage(def_stage, false)
+        } else {
+            def_stage
+        };
+        let defense = apply_stat_stage(defense, def_stage);
+
+        // Weather only touches Fire and Water moves, which are special
+        let weather_mult = match (base_weather(weather), move_type) {
+            (WEATHER_SUN, PokemonType::Fire) | (WEATHER_RAIN, PokemonType::Water) => 1.5,
+            (WEATHER_SUN, PokemonType::Water) | (WEATHER_RAIN, PokemonType::Fire) => 0.5,
+            _ => 1.0,
+        };
+        let screen = !critical
+            && if physical {
+                self.field.reflect
+            } else {
+                self.field.light_screen
+            };
+        let stab = attacker.types.contains(&move_type);
+        let mods = Gen3Modifiers {
+            burned: physical && attacker.status == Status::Burn && atk_ability != "guts",
+            screen,
+            doubles: self.field.doubles,
+            spread: self.field.spread,
+            weather: weather_mult,
+            critical,
+            helping_hand: self.field.helping_hand,
+            stab,
+            effectiveness,
+        };
+        let rolls = match fixed_damage(&move_name, &move_ctx) {
+            _ if self.field.protect => [0; NUM_ROLLS],
+            Some(damage) if effectiveness[0] * effectiveness[1] > 0.0 => [damage; NUM_ROLLS],
+            Some(_) => [0; NUM_ROLLS],
+            None => gen3_damage_rolls(attacker.level, power, attack, defense, &mods),
+        };
+
+        let mut result = DamageCalcResult {
+            rolls: rolls.to_vec(),
+            defender_hp: def_stats.hp,
+            effectiveness: effectiveness[0] * effectiveness[1],
+            immunity: immunity as u8,
+            stab: if stab { 1.5 } else { 1.0 },
+            power,
+            attack,
+            defense,
+            ..Default::default()
+        };
+        result.apply_hit_counts(&self.move_hit_counts());
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::{CalcField, CalcMove, CalcPokemon, MoveCategory};
+    use crate::test_utils::{self, attack, mon_with_stats};
+    use pkmn_stats::StatBlock;
+
+    fn gen3_field() -> CalcField {
+        CalcField {
+            generation: GEN3,
+            ..Default::default()
+        }
+    }
+
+    /// `test_utils::request` with Gen 3 mechanics. The category is ignored
+    /// in Gen 3, so every move is given as special.
+    fn request(move_type: PokemonType, power: u32) -> DamageCalcRequest {
+        DamageCalcRequest {
+            field: gen3_field(),
+            ..test_utils::request(attack(move_type, power, MoveCategory::Special))
+        }
+    }
+
+    #[test]
+    fn test_known_gen3_calc() {
+        // 252+ Atk Choice Band Salamence Double-Edge vs 252 HP / 0 Def Skarmory
+        let req = DamageCalcRequest {
+            attacker: CalcPokemon {
+                level: 100,
+                types: vec![PokemonType::Dragon, PokemonType::Flying],
+                base_stats: StatBlock::from_array([95, 135, 80, 110, 80, 100]),
+                evs: StatBlock::from_array([0, 252, 0, 4, 0, 252]),
+                nature: "Adamant".to_string(),
+                item: "Choice Band".to_string(),
+                ..Default::default()
+            },
+            defender: CalcPokemon {
+                level: 100,
+                types: vec![PokemonType::Steel, PokemonType::Flying],
+                base_stats: StatBlock::from_array([65, 80, 140, 40, 70, 70]),
+                evs: StatBlock::from_array([252, 0, 0, 0, 4, 252]),
+                ..Default::default()
+            },
+            r#move: CalcMove {
+                name: "Double-Edge".to_string(),
+                ..attack(PokemonType::Normal, 120, MoveCategory::Special)
+            },
+            field: gen3_field(),
+        };
+        // Attack 405 * 1.5 = 607 vs Defense 316, 334 HP:
+        // floor(floor(607 * 120 * 42 / 316) / 50) + 2 = 195, resisted: 97
+        let result = req.calculate().unwrap();
+        assert_eq!(
+            (result.attack, result.defense, result.defender_hp),
+            (607, 316, 334)
+        );
+        assert_eq!((result.min, result.max), (82, 97));
+    }
+
+    #[test]
+    fn test_split_by_type() {
+        // Shadow Ball is physical and Crunch special in Gen 3
+        let mut req = request(PokemonType::Ghost, 80);
+        req.attacker = mon_with_stats(&[PokemonType::Water], [200, 150, 100, 50, 100, 100]);
+        // Normal is immune to Ghost
+        assert_eq!(req.calculate().unwrap().attack, 150);
+        req.r#move.move_type = PokemonType::Dark;
+        assert_eq!(req.calculate().unwrap().attack, 50);
+    }
+
+    #[test]
+    fn test_critical_hit_and_fairy() {
+        // floor(floor(100 * 80 * 22 / 100) / 50) + 2 = 37, 2x on a crit
+        let mut req = request(PokemonType::Normal, 80);
+        assert_eq!(req.calculate().unwrap().max, 37);
+        req.r#move.critical = true;
+        assert_eq!(req.calculate().unwrap().max, 74);
+
+        // No Fairy type: neutral both ways
+        req.r#move.move_type = PokemonType::Fairy;
+        req.defender.types = vec![PokemonType::Dragon];
+        assert_eq!(req.calculate().unwrap().effectiveness, 1.0);
+        assert_eq!(gen3_critical_hit_chance(0), 1.0 / 16.0);
+    }
+
+    #[test]
+    fn test_weather_before_stab() {
+        // Sun: floor(35 * 1.5) = 52, + 2 = 54, STAB: 81
+        let mut req = request(PokemonType::Fire, 80);
+        req.field.weather = pkmn_stats::WEATHER_SUN;
+        assert_eq!(req.calculate().unwrap().max, 81);
+
+        // Type items boost the stat by 10% before stages
+        req.field.weather = 0;
+        req.attacker.item = "Charcoal".to_string();
+        req.attacker.boosts.sp_atk = 1;
+        assert_eq!(req.calculate().unwrap().attack, 165);
+    }
+
+    #[test]
+    fn test_helping_hand_after_critical() {
+        // Applied to the damage after the +2, not to power: (35 + 2) * 1.5 = 55
+        let mut req = request(PokemonType::Normal, 80);
+        req.field.helping_hand = true;
+        let result = req.calculate().unwrap();
+        assert_eq!((result.power, result.max), (80, 55));
+        // (35 + 2) * 2 * 1.5 = 111
+        req.r#move.critical = true;
+        assert_eq!(req.calculate().unwrap().max, 111);
+    }
+
+    #[test]
+    fn test_fixed_and_variable_power() {
+        let mut req = request(PokemonType::Fighting, 0);
+        req.r#move.name = "Seismic Toss".to_string();
+        assert_eq!(req.calculate().unwrap().rolls, vec![50; NUM_ROLLS]);
+        req.defender.types = vec![PokemonType::Ghost];
+        assert_eq!(req.calculate().unwrap().max, 0);
+
+        req.defender.types = vec![PokemonType::Normal];
+        req.r#move = attack(PokemonType::Normal, 0, MoveCategory::Special);
+        req.r#move.name = "Super Fang".to_string();
+        req.defender.current_hp = Some(151);
+        assert_eq!(req.calculate().unwrap().max, 75);
+        req.r#move.name = "Sonic Boom".to_string();
+        assert_eq!(req.calculate().unwrap().max, 20);
+
+        // 2100 hg: 120 power Low Kick
+        req.r#move = attack(PokemonType::Fighting, 0, MoveCategory::Special);
+        req.r#move.name = "Low Kick".to_string();
+        req.defender.weight = 2100;
+        assert_eq!(req.calculate().unwrap().power, 120);
+
+        req.r#move = attack(PokemonType::Normal, 0, MoveCategory::Special);
+        req.r#move.name = "Flail".to_string();
+        req.attacker.current_hp = Some(1);
+        assert_eq!(req.calculate().unwrap().power, 200);
+        req.r#move = attack(PokemonType::Fire, 150, MoveCategory::Special);
+        req.r#move.name = "Eruption".to_string();
+        req.attacker.current_hp = Some(100);
+        assert_eq!(req.calculate().unwrap().power, 75);
+    }
+}
@@ -0,0 +1,475 @@
+use pkmn_stats::{
+    base_weather, WEATHER_HAIL, WEATHER_RAIN, WEATHER_SAND, WEATHER_SNOW, WEATHER_SUN,
+};
+use pkmn_type_chart::{defensive_multiplier, normalize_name, PokemonType};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+/// Highest number of hits `ko_chance` looks at.
+pub const MAX_KO_HITS: u32 = 4;
+
+/// Chance of a critical hit at a critical hit stage (Gen 7+): 1/24, 1/8, 1/2, then always.
+pub fn critical_hit_chance(stage: u8) -> f64 {
+    match stage {
+        0 => 1.0 / 24.0,
+        1 => 1.0 / 8.0,
+        2 => 0.5,
+        _ => 1.0,
+    }
+}
+
+/// Entry hazards and end-of-turn effects on the defender.
+///
+/// Ability and item names are matched case-insensitively, ignoring spaces
+/// and hyphens. Empty strings mean "none".
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ResidualSources {
+    /// The defender switched into Stealth Rock before the first hit.
+    pub stealth_rock: bool,
+    /// 0 = none, 1 = sun, 2 = rain, 3 = sandstorm, 4 = hail, 5 = snow,
+    /// 6 = harsh sunlight, 7 = heavy rain. Snow heals Ice Body but deals no
+    /// chip damage.
+    pub weather: u8,
+    /// Defender's types, for Stealth Rock, weather immunities and Black Sludge.
+    pub types: Vec<PokemonType>,
+    pub ability: String,
+    /// Leftovers, Black Sludge, or Life Orb (the defender attacks back every turn).
+    pub item: String,
+}
+
+/// A change to the defender's HP between hits, as a fraction of max HP.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Residual {
+    /// Positive heals, negative damages.
+    numerator: i64,
+    denominator: i64,
+    label: &'static str,
+}
+
+impl Residual {
+    fn damage(denominator: i64, label: &'static str) -> Self {
+        Residual {
+            numerator: -1,
+            denominator,
+            label,
+        }
+    }
+
+    fn heal(denominator: i64, label: &'static str) -> Self {
+        Residual {
+            numerator: 1,
+            denominator,
+            label,
+        }
+    }
+
+    /// HP change for a Pokemon with `max_hp`; at least 1 HP either way.
+    fn amount(self, max_hp: u32) -> i64 {
+        self.numerator * (max_hp as i64 / self.denominator).max(1)
+    }
+}
+
+impl ResidualSources {
+    fn has_type(&self, t: PokemonType) -> bool {
+        self.types.contains(&t)
+    }
+
+    /// Stealth Rock damage for a Pokemon with `max_hp`, or 0 if it does not apply.
+    fn stealth_rock_damage(&self, max_hp: u32) -> u32 {
+        let ability = normalize_name(&self.ability);
+        let item = normalize_name(&self.item);
+        if !self.stealth_rock || ability == "magicguard" || item == "heavydutyboots" {
+            return 0;
+        }
+        let Some(&type1) = self.types.first() else {
+            return max_hp / 8;
+        };
+        let effectiveness =
+            defensive_multiplier(PokemonType::Rock, type1, self.types.get(1).copied());
+        ((max_hp as f64 * effectiveness / 8.0) as u32).max(1)
+    }
+
+    /// End-of-turn HP changes in the order they happen: Life Orb recoil from
+    /// attacking, weather, then held item recovery.
+    fn end_of_turn(&self) -> Vec<Residual> {
+        let ability = normalize_name(&self.ability);
+        let item = normalize_name(&self.item);
+        let magic_guard = ability == "magicguard";
+        let weather_immune = ability == "overcoat" || item == "safetygoggles";
+        let mut residuals = Vec::new();
+
+        if item == "lifeorb" && !magic_guard {
+            residuals.push(Residual::damage(10, "Life Orb recoil"));
+        }
+
+        match (base_weather(self.weather), ability.as_str()) {
+            (WEATHER_SAND, "sandveil" | "sandrush" | "sandforce") => {}
+            (WEATHER_SAND, _)
+                if !magic_guard
+                    && !weather_immune
+                    && ![PokemonType::Rock, PokemonType::Ground, PokemonType::Steel]
+                        .iter()
+                        .any(|&t| self.has_type(t)) =>
+            {
+                residuals.push(Residual::damage(16, "sandstorm damage"));
+            }
+            (WEATHER_HAIL, "icebody") => residuals.push(Residual::heal(16, "Ice Body recovery")),
+            (WEATHER_HAIL, "snowcloak") => {}
+            (WEATHER_HAIL, _)
+                if self.weather != WEATHER_SNOW
+                    && !magic_guard && !weather_immune && !self.has_type(PokemonType::Ice) =>
+            {
+                residuals.push(Residual::damage(16, "hail damage"));
+            }
+            (WEATHER_RAIN, "raindish") => residuals.push(Residual::heal(16, "Rain Dish recovery")),
+            (WEATHER_RAIN, "dryskin") => residuals.push(Residual::heal(8, "Dry Skin recovery")),
+            (WEATHER_SUN, "dryskin") if !magic_guard => {
+                residuals.push(Residual::damage(8, "Dry Skin damage"));
+            }
+            (WEATHER_SUN, "solarpower") if !magic_guard => {
+                residuals.push(Residual::damage(8, "Solar Power damage"));
+            }
+            _ => {}
+        }
+
+        match item.as_str() {
+            "leftovers" => residuals.push(Residual::heal(16, "Leftovers recovery")),
+            "blacksludge" if self.has_type(PokemonType::Poison) => {
+                residuals.push(Residual::heal(16, "Black Sludge recovery"));
+            }
+            "blacksludge" if !magic_guard => {
+                residuals.push(Residual::damage(8, "Black Sludge damage"))
+            }
+            _ => {}
+        }
+        residuals
+    }
+}
+
+/// Probability of knocking the defender out within `hits` hits.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KoChance {
+    /// Probability (0-1) of a KO by the end of turn `hits`.
+    pub chance: f64,
+    /// Fewest hits with a chance to KO, or 0 if none within `MAX_KO_HITS`.
+    pub hits: u32,
+    /// Summary such as "75% chance to 2HKO after Stealth Rock", or "" if
+    /// there is no KO within `MAX_KO_HITS` hits.
+    pub text: String,
+}
+
+/// Probability distribution over the defender's current HP (index = HP).
+/// Fainted outcomes are moved into `fainted`.
+struct HpDistribution {
+    alive: Vec<f64>,
+    fainted: f64,
+}
+
+impl HpDistribution {
+    fn new(max_hp: u32, current_hp: u32) -> Self {
+        let mut alive = vec![0.0; max_hp as usize + 1];
+        alive[current_hp.min(max_hp) as usize] = 1.0;
+        HpDistribution {
+            alive,
+            fainted: 0.0,
+        }
+    }
+
+    /// Apply every `(hp_change, probability)` outcome to every HP value.
+    fn apply(&mut self, outcomes: &[(i64, f64)]) {
+        let max_hp = self.alive.len() as i64 - 1;
+        let mut next = vec![0.0; self.alive.len()];
+        for (hp, &p) in self.alive.iter().enumerate() {
+            if p == 0.0 {
+                continue;
+            }
+            for &(change, q) in outcomes {
+                let hp = hp as i64 + change;
+                if hp <= 0 {
+                    self.fainted += p * q;
+                } else {
+                    next[hp.min(max_hp) as usize] += p * q;
+                }
+            }
+        }
+        self.alive = next;
+    }
+}
+
+/// "OHKO", "2HKO", ...
+fn ko_label(hits: u32) -> String {
+    if hits == 1 {
+        "OHKO".to_string()
+    } else {
+        format!("{}HKO", hits)
+    }
+}
+
+/// "a", "a and b", "a, b and c".
+fn join_labels(labels: &[&str]) -> String {
+    match labels.split_last() {
+        None => String::new(),
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+/// Damage distribution of a single hit as `(damage, probability)` pairs
+/// sorted by damage: a critical hit with probability `crit_chance`, then one
+/// of `rolls` (or `crit_rolls`) with equal probability.
+pub fn hit_distribution(rolls: &[u32], crit_rolls: &[u32], crit_chance: f64) -> Vec<(u32, f64)> {
+    let crit_chance = if crit_rolls.is_empty() {
+        0.0
+    } else {
+        crit_chance.clamp(0.0, 1.0)
+    };
+    let mut hit: Vec<(u32, f64)> = Vec::new();
+    let weighted = rolls
+        .iter()
+        .map(|&d| (d, (1.0 - crit_chance) / rolls.len() as f64))
+        .chain(
+            crit_rolls
+                .iter()
+                .map(|&d| (d, crit_chance / crit_rolls.len() as f64)),
+        );
+    for (damage, p) in weighted {
+        match hit.iter_mut().find(|(d, _)| *d == damage) {
+            Some(entry) => entry.1 += p,
+            None => hit.push((damage, p)),
+        }
+    }
+    hit.sort_by_key(|&(d, _)| d);
+    hit
+}
+
+/// Exact probability of an nHKO, for the smallest n up to `MAX_KO_HITS`.
+///
+/// Every hit is a critical hit with probability `crit_chance`, then deals one
+/// of `rolls` (or `crit_rolls`) with equal probability. Stealth Rock is taken
+/// once before the first hit; end-of-turn effects follow every hit, and can
+/// finish the defender off.
+pub fn ko_chance(
+    rolls: &[u32],
+    crit_rolls: &[u32],
+    crit_chance: f64,
+    max_hp: u32,
+    current_hp: u32,
+    residual: &ResidualSources,
+) -> KoChance {
+    let hit = hit_distribution(rolls, crit_rolls, crit_chance);
+    ko_chance_from_distribution(&hit, max_hp, current_hp, residual)
+}
+
+/// `ko_chance` for any per-turn damage distribution, given as `(damage,
+/// probability)` pairs (see `hit_distribution` and `multi_hit_distribution`).
+pub fn ko_chance_from_distribution(
+    hit: &[(u32, f64)],
+    max_hp: u32,
+    current_hp: u32,
+    residual: &ResidualSources,
+) -> KoChance {
+    let none = KoChance {
+        chance: 0.0,
+        hits: 0,
+        text: String::new(),
+    };
+    if hit.is_empty() || max_hp == 0 {
+        return none;
+    }
+    let hit: Vec<(i64, f64)> = hit.iter().map(|&(d, p)| (-(d as i64), p)).collect();
+
+    let mut hp = HpDistribution::new(max_hp, current_hp);
+    let mut labels = Vec::new();
+    let stealth_rock = residual.stealth_rock_damage(max_hp);
+    if stealth_rock > 0 {
+        hp.apply(&[(-(stealth_rock as i64), 1.0)]);
+        labels.push("Stealth Rock");
+    }
+    let end_of_turn = residual.end_of_turn();
+
+    for hits in 1..=MAX_KO_HITS {
+        hp.apply(&hit);
+        for r in &end_of_turn {
+            hp.apply(&[(r.amount(max_hp), 1.0)]);
+            if !labels.contains(&r.label) {
+                labels.push(r.label);
+            }
+        }
+        if hp.fainted <= 0.0 {
+            continue;
+        }
+
+        let chance = hp.fainted.min(1.0);
+        let mut text = if chance >= 1.0 - 1e-9 {
+            format!("guaranteed {}", ko_label(hits))
+        } else {
+            let percent = ((chance * 1000.0).round() / 10.0).clamp(0.1, 99.9);
+            format!("{}% chance to {}", percent, ko_label(hits))
+        };
+        if !labels.is_empty() {
+            text = format!("{} after {}", text, join_labels(&labels));
+        }
+        return KoChance { chance, hits, text };
+    }
+    none
+}
+
+/// Parse an optional `ResidualSources` from JS: undefined and null mean none.
+pub(crate) fn residual_from_js(residual: JsValue) -> Option<ResidualSources> {
+    if residual.is_undefined() || residual.is_null() {
+        Some(ResidualSources::default())
+    } else {
+        serde_wasm_bindgen::from_value(residual).ok()
+    }
+}
+
+/// Exact KO chance of an attack, including critical hits and residual damage.
+///
+/// Parameters:
+/// - `rolls`: the 16 damage rolls of a regular hit (see `calculate_damage_rolls`)
+/// - `crit_rolls`: the 16 damage rolls of a critical hit (empty to ignore crits)
+/// - `crit_stage`: critical hit stage (0 = 1/24, 1 = 1/8, 2 = 1/2, 3+ = always)
+/// - `max_hp`, `current_hp`: the defender's HP
+/// - `residual`: `{ stealthRock, weather, types, ability, item }` (all optional)
+///
+/// Returns `{ chance, hits, text }`, e.g. `{ chance: 0.75, hits: 2,
+/// text: "75% chance to 2HKO after Stealth Rock" }`, or null if `residual`
+/// is malformed.
+#[wasm_bindgen]
+pub fn calculate_ko_chance(
+    rolls: &[u32],
+    crit_rolls: &[u32],
+    crit_stage: u8,
+    max_hp: u32,
+    current_hp: u32,
+    residual: JsValue,
+) -> JsValue {
+    let Some(residual) = residual_from_js(residual) else {
+        return JsValue::NULL;
+    };
+    let result = ko_chance(
+        rolls,
+        crit_rolls,
+        critical_hit_chance(crit_stage),
+        max_hp,
+        current_hp,
+        &residual,
+    );
+    serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_residual() -> ResidualSources {
+        ResidualSources::default()
+    }
+
+    /// Half the rolls deal 45, half 55.
+    fn split_rolls() -> Vec<u32> {
+        [45; 8].into_iter().chain([55; 8]).collect()
+    }
+
+    #[test]
+    fn test_guaranteed_and_split() {
+        let ko = ko_chance(&[100; 16], &[], 0.0, 100, 100, &no_residual());
+        assert_eq!((ko.hits, ko.chance), (1, 1.0));
+        assert_eq!(ko.text, "guaranteed OHKO");
+
+        // Only 45 + 45 survives two hits
+        let ko = ko_chance(&split_rolls(), &[], 0.0, 100, 100, &no_residual());
+        assert_eq!(ko.hits, 2);
+        assert!((ko.chance - 0.75).abs() < 1e-12);
+        assert_eq!(ko.text, "75% chance to 2HKO");
+
+        let ko = ko_chance(&[10; 16], &[], 0.0, 100, 100, &no_residual());
+        assert_eq!(ko.hits, 0);
+        assert_eq!(ko.text, "");
+    }
+
+    #[test]
+    fn test_crit_chance() {
+        let ko = ko_chance(
+            &[60; 16],
+            &[100; 16],
+            critical_hit_chance(0),
+            100,
+            100,
+            &no_residual(),
+        );
+        assert_eq!(ko.hits, 1);
+        assert_eq!(ko.text, "4.2% chance to OHKO");
+        // Without a crit it is a guaranteed 2HKO, so the OHKO row wins
+        assert_eq!(critical_hit_chance(3), 1.0);
+        let ko = ko_chance(&[60; 16], &[100; 16], 1.0, 100, 100, &no_residual());
+        assert_eq!(ko.text, "guaranteed OHKO");
+    }
+
+    #[test]
+    fn test_stealth_rock() {
+        let mut residual = ResidualSources {
+            stealth_rock: true,
+            types: vec![PokemonType::Normal],
+            ..Default::default()
+        };
+        // 12 from Stealth Rock leaves 88: any two hits KO
+        let ko = ko_chance(&split_rolls(), &[], 0.0, 100, 100, &residual);
+        assert_eq!(ko.text, "guaranteed 2HKO after Stealth Rock");
+
+        // 4x weak to Rock: 50 damage on entry
+        residual.types = vec![PokemonType::Fire, PokemonType::Flying];
+        assert_eq!(residual.stealth_rock_damage(100), 50);
+        residual.item = "Heavy-Duty Boots".to_string();
+        assert_eq!(residual.stealth_rock_damage(100), 0);
+        residual.item.clear();
+        residual.ability = "Magic Guard".to_string();
+        assert_eq!(residual.stealth_rock_damage(100), 0);
+    }
+
+    #[test]
+    fn test_recovery_and_chip() {
+        let mut residual = ResidualSources {
+            item: "Leftovers".to_string(),
+            types: vec![PokemonType::Water],
+            ..Default::default()
+        };
+        // 50 -> 56 -> 6 -> 12 -> KO
+        let ko = ko_chance(&[50; 16], &[], 0.0, 100, 100, &residual);
+        assert_eq!(ko.text, "guaranteed 3HKO after Leftovers recovery");
+
+        residual.weather = WEATHER_SAND;
+        residual.item = "Life Orb".to_string();
+        // 100 -> 51 -> 41 (recoil) -> 35 (sand) -> KO
+        let ko = ko_chance(&[49; 16], &[], 0.0, 100, 100, &residual);
+        assert_eq!(
+            ko.text,
+            "guaranteed 2HKO after Life Orb recoil and sandstorm damage"
+        );
+
+        residual.types = vec![PokemonType::Steel];
+        residual.item = "Black Sludge".to_string();
+        let ko = ko_chance(&[49; 16], &[], 0.0, 100, 100, &residual);
+        assert_eq!(ko.text, "guaranteed 2HKO after Black Sludge damage");
+    }
+
+    #[test]
+    fn test_end_of_turn_can_finish() {
+        // 97 damage leaves 3; hail chip (6) knocks out at the end of turn 1
+        let mut residual = ResidualSources {
+            weather: WEATHER_HAIL,
+            types: vec![PokemonType::Dragon],
+            ..Default::default()
+        };
+        let ko = ko_chance(&[97; 16], &[], 0.0, 100, 100, &residual);
+        assert_eq!(ko.text, "guaranteed OHKO after hail damage");
+
+        // Snow has no chip damage
+        residual.weather = WEATHER_SNOW;
+        let ko = ko_chance(&[97; 16], &[], 0.0, 100, 100, &residual);
+        assert_eq!(ko.text, "guaranteed 2HKO");
+        assert_eq!(join_labels(&["a", "b", "c"]), "a, b and c");
+    }
+}
@@ -1,29 +1,77 @@
+use pkmn_stats::{
+    apply_stat_stage, base_weather, critical_hit_stage, WEATHER_HARSH_SUN, WEATHER_HEAVY_RAIN,
+    WEATHER_RAIN, WEATHER_SUN,
+};
+use pkmn_type_chart::{PokemonType, TypeChart};
 use wasm_bindgen::prelude::*;
 
-/// Apply a stat stage modifier (-6 to +6) to a base stat value.
-///
-/// For critical hits:
-/// - `favorable = true` (attacker's offensive stat): ignores negative stages
-/// - `favorable = false` (defender's defensive stat): ignores positive stages
-fn apply_stat_stage(stat: f64, stage: i8, is_critical: bool, favorable: bool) -> f64 {
-    let effective_stage = if is_critical {
-        if favorable {
-            stage.max(0)
-        } else {
-            stage.min(0)
-        }
+mod doubles;
+mod effects;
+mod field;
+mod formula;
+mod gen3;
+mod ko_chance;
+mod matrix;
+mod max_moves;
+mod request;
+mod secondary_damage;
+mod special_moves;
+#[cfg(test)]
+mod test_utils;
+
+pub use doubles::{
+    calculate_doubles_damage, DoublesCalcRequest, DoublesTarget, DoublesTargetResult, MoveTarget,
+    Redirection, MAX_DOUBLES_TARGETS,
+};
+pub use field::{
+    effective_weather, is_grounded, screen_modifier, terrain_modifier, weather_defense_modifier,
+    weather_modifier, TERRAIN_ELECTRIC, TERRAIN_GRASSY, TERRAIN_MISTY, TERRAIN_PSYCHIC,
+};
+pub use formula::{
+    base_damage, calculate_damage_rolls, chain_modifiers, damage_rolls, DamageModifiers, NUM_ROLLS,
+};
+pub use gen3::{
+    gen3_critical_hit_chance, gen3_damage_rolls, is_gen3_physical_type, Gen3Modifiers, GEN3,
+};
+pub use ko_chance::{
+    calculate_ko_chance, critical_hit_chance, hit_distribution, ko_chance,
+    ko_chance_from_distribution, KoChance, ResidualSources, MAX_KO_HITS,
+};
+pub use matrix::{calculate_damage_matrix, DamageMatrixRequest, MatrixCell, TeamMember};
+pub use max_moves::{
+    dynamax_hp, max_move_effect, max_move_power, max_move_secondary_effect, z_move_power,
+    MaxMoveEffect, MoveMode, MAX_DYNAMAX_LEVEL,
+};
+pub use request::{
+    calculate_damage_request, calculate_ko_chance_request, CalcField, CalcMove, CalcPokemon,
+    DamageCalcRequest, DamageCalcResult, MoveCategory, StatStages, Status,
+};
+pub use secondary_damage::{
+    confusion_damage, contact_damage, drain_fraction, drain_hp, recoil_damage, recoil_fraction,
+    status_damage, CONFUSION_POWER, MAX_TOXIC_COUNTER,
+};
+pub use pkmn_stats::{fixed_damage, variable_power, MoveContext};
+pub use special_moves::{hit_counts, multi_hit_distribution};
+
+/// Apply a stat stage to an attacking or defending stat via the shared
+/// pkmn-stats pipeline, dropping the stages a critical hit ignores
+/// (see `critical_hit_stage`).
+fn staged_stat(stat: u16, stage: i8, is_critical: bool, favorable: bool) -> f64 {
+    let stage = if is_critical {
+        critical_hit_stage(stage, favorable)
     } else {
         stage
     };
-    if effective_stage >= 0 {
-        (stat * (2.0 + effective_stage as f64) / 2.0).floor()
-    } else {
-        (stat * 2.0 / (2.0 - effective_stage as f64)).floor()
-    }
+    apply_stat_stage(stat as u32, stage) as f64
 }
 
 /// Calculate damage given pre-resolved numeric inputs.
 /// The TS wrapper handles extracting stats, looking up items/abilities, etc.
+/// `calculate_damage_request` resolves those in Rust instead.
+///
+/// This is the simplified level 50 formula with min/max rolls only. Use
+/// `calculate_damage_rolls` for other levels, the full modifier chain and
+/// all 16 rolls.
 ///
 /// Parameters:
 /// - `effective_atk`: The attacker's calculated atk or spAtk stat (nature/EVs/IVs applied)
@@ -34,7 +82,8 @@ fn apply_stat_stage(stat: f64, stage: i8, is_critical: bool, favorable: bool) ->
 /// - `def_type2`: Defender's second type index, or 255 for mono-type
 /// - `stab`: STAB multiplier (1.0, 1.5, or 2.0 for Tera), pre-calculated by TS
 /// - `is_critical`: Whether this is a critical hit
-/// - `weather`: 0=none, 1=sun, 2=rain, 3=sandstorm, 4=hail
+/// - `weather`: 0=none, 1=sun, 2=rain, 3=sandstorm, 4=hail, 5=snow,
+///   6=harsh sunlight, 7=heavy rain
 /// - `move_is_fire`: Whether the move is Fire type (for weather interaction)
 /// - `move_is_water`: Whether the move is Water type (for weather interaction)
 /// - `item_damage_mult`: Pre-resolved item damage multiplier (1.0 if no item applies)
@@ -48,6 +97,7 @@ fn apply_stat_stage(stat: f64, stage: i8, is_critical: bool, favorable: bool) ->
 /// Returns a `Vec<f64>` of 5 values:
 /// `[min_damage, max_damage, effectiveness, stab_was_applied, is_critical]`
 #[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
 pub fn calculate_damage(
     effective_atk: u16,
     effective_def: u16,
@@ -67,18 +117,71 @@ pub fn calculate_damage(
     def_stage: i8,
     def_item_spdef_mult: f64,
     is_physical: bool,
+) -> Vec<f64> {
+    calculate_damage_with_chart(
+        &TypeChart::default(),
+        effective_atk,
+        effective_def,
+        move_power,
+        move_type,
+        def_type1,
+        def_type2,
+        stab,
+        is_critical,
+        weather,
+        move_is_fire,
+        move_is_water,
+        item_damage_mult,
+        ability_atk_mult,
+        is_burned_physical,
+        atk_stage,
+        def_stage,
+        def_item_spdef_mult,
+        is_physical,
+    )
+}
+
+/// Same as `calculate_damage`, but type effectiveness is read from `chart`
+/// (e.g. an Inverse Battle or older-generation chart).
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_damage_with_chart(
+    chart: &TypeChart,
+    effective_atk: u16,
+    effective_def: u16,
+    move_power: u16,
+    move_type: u8,
+    def_type1: u8,
+    def_type2: u8,
+    stab: f64,
+    is_critical: bool,
+    weather: u8,
+    move_is_fire: bool,
+    move_is_water: bool,
+    item_damage_mult: f64,
+    ability_atk_mult: f64,
+    is_burned_physical: bool,
+    atk_stage: i8,
+    def_stage: i8,
+    def_item_spdef_mult: f64,
+    is_physical: bool,
 ) -> Vec<f64> {
     // 1. Apply stat stages
-    let mut atk =
-        apply_stat_stage(effective_atk as f64, atk_stage, is_critical, true);
-    let mut def =
-        apply_stat_stage(effective_def as f64, def_stage, is_critical, false);
+    let mut atk = staged_stat(effective_atk, atk_stage, is_critical, true);
+    let mut def = staged_stat(effective_def, def_stage, is_critical, false);
 
     // 2. Apply Assault Vest (special defense item multiplier) for special moves
     if !is_physical && def_item_spdef_mult > 1.0 {
         def = (def * def_item_spdef_mult).floor();
     }
 
+    // 2b. Sandstorm (Rock Sp. Def) and snow (Ice Defense) boosts
+    let def_types: Vec<PokemonType> = [def_type1, def_type2]
+        .into_iter()
+        .filter_map(PokemonType::from_index)
+        .collect();
+    def = (def * weather_defense_modifier(weather, &def_types, is_physical)).floor();
+
     // 3. Apply burn penalty
     if is_burned_physical {
         atk = (atk * 0.5).floor();
@@ -88,9 +191,10 @@ pub fn calculate_damage(
     atk = (atk * ability_atk_mult).floor();
 
     // 5. Type effectiveness via pkmn-type-chart
-    let def_type2_signed: i8 = if def_type2 == 255 { -1 } else { def_type2 as i8 };
-    let type_eff =
-        pkmn_type_chart::get_defensive_multiplier(move_type, def_type1, def_type2_signed);
+    let type_eff = match (PokemonType::from_index(move_type), PokemonType::from_index(def_type1)) {
+        (Some(atk), Some(def1)) => chart.matchup(atk, def1, PokemonType::from_index(def_type2)),
+        _ => 1.0,
+    };
 
     // 6. Base damage formula (level 50)
     let power = move_power as f64;
@@ -102,27 +206,13 @@ pub fn calculate_damage(
     let mut modified = (base * stab * type_eff).floor();
 
     // 8. Weather modifier
-    let weather_mult = match weather {
-        1 => {
-            // Sun
-            if move_is_fire {
-                1.5
-            } else if move_is_water {
-                0.5
-            } else {
-                1.0
-            }
-        }
-        2 => {
-            // Rain
-            if move_is_water {
-                1.5
-            } else if move_is_fire {
-                0.5
-            } else {
-                1.0
-            }
-        }
+    let weather_mult = match (weather, move_is_fire, move_is_water) {
+        // Water moves fail in harsh sunlight, Fire moves in heavy rain
+        (WEATHER_HARSH_SUN, _, true) | (WEATHER_HEAVY_RAIN, true, _) => 0.0,
+        (w, true, _) if base_weather(w) == WEATHER_SUN => 1.5,
+        (w, _, true) if base_weather(w) == WEATHER_RAIN => 1.5,
+        (w, true, _) if base_weather(w) == WEATHER_RAIN => 0.5,
+        (w, _, true) if base_weather(w) == WEATHER_SUN => 0.5,
         _ => 1.0,
     };
     modified = (modified * weather_mult).floor();
@@ -528,4 +618,42 @@ mod tests {
         assert_eq!(result[0], 46.0, "min damage rain+water");
         assert_eq!(result[1], 55.0, "max damage rain+water");
     }
+
+    #[test]
+    fn test_primal_weather_and_sand() {
+        // Fire move in heavy rain fails outright
+        let result = calc(TestParams {
+            move_type: Some(1),
+            move_is_fire: Some(true),
+            weather: Some(7),
+            ..Default::default()
+        });
+        assert_eq!(result[1], 0.0, "fire in heavy rain");
+
+        // Water special move vs Rock in sand: def = floor(100 * 1.5) = 150
+        // base = floor((22 * 80 * 100 / 150) / 50 + 2) = 25, x2 = 50
+        let result = calc(TestParams {
+            move_type: Some(2),
+            def_type1: Some(12),
+            weather: Some(3),
+            is_physical: Some(false),
+            ..Default::default()
+        });
+        assert_eq!(result[0], 42.0, "min damage sand rock");
+        assert_eq!(result[1], 50.0, "max damage sand rock");
+    }
+
+    #[test]
+    fn test_inverse_chart_flips_effectiveness() {
+        // Fire (1) vs Grass (4) is resisted in an Inverse Battle
+        let chart = TypeChart::inverse(6);
+        let result = calculate_damage_with_chart(
+            &chart, 100, 100, 80, 1, 4, 255, 1.0, false, 0, true, false, 1.0, 1.0, false, 0, 0,
+            1.0, true,
+        );
+        // modified = floor(37 * 0.5) = 18, min = floor(18 * 0.85) = 15
+        assert_eq!(result[0], 15.0, "min damage inverse");
+        assert_eq!(result[1], 18.0, "max damage inverse");
+        assert_eq!(result[2], 0.5, "type effectiveness inverted");
+    }
 }
@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::ko_chance::MAX_KO_HITS;
+use crate::request::{CalcField, CalcMove, CalcPokemon, DamageCalcRequest};
+
+/// A team member and the moves it attacks with.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TeamMember {
+    pub pokemon: CalcPokemon,
+    pub moves: Vec<CalcMove>,
+}
+
+/// Every move of `attackers` against every member of `defenders`.
+/// Defenders' moves are ignored.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DamageMatrixRequest {
+    pub attackers: Vec<TeamMember>,
+    pub defenders: Vec<TeamMember>,
+    pub field: CalcField,
+}
+
+/// One attacker × move × defender entry of the matrix.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MatrixCell {
+    pub min_percent: f64,
+    pub max_percent: f64,
+    /// Fewest hits that can KO from the defender's current HP (1 = OHKO),
+    /// or 0 if it takes more than `MAX_KO_HITS`.
+    pub ko: u8,
+    /// The KO in `ko` hits happens even at the lowest roll.
+    pub guaranteed: bool,
+}
+
+impl MatrixCell {
+    fn new(min: u32, max: u32, min_percent: f64, max_percent: f64, hp: u32) -> Self {
+        let ko = (1..=MAX_KO_HITS)
+            .find(|&hits| max as u64 * hits as u64 >= hp as u64)
+            .unwrap_or(0);
+        MatrixCell {
+            min_percent,
+            max_percent,
+            ko: ko as u8,
+            guaranteed: ko > 0 && min as u64 * ko as u64 >= hp as u64,
+        }
+    }
+}
+
+impl DamageMatrixRequest {
+    /// Compute the matrix, indexed `[attacker][move][defender]`.
+    ///
+    /// Returns `None` if any calculation fails (see
+    /// `DamageCalcRequest::calculate`).
+    pub fn calculate(&self) -> Option<Vec<Vec<Vec<MatrixCell>>>> {
+        self.attackers
+            .iter()
+            .map(|attacker| {
+                attacker
+                    .moves
+                    .iter()
+                    .map(|mv| {
+                        self.defenders
+                            .iter()
+                            .map(|defender| self.cell(attacker, mv, &defender.pokemon))
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn cell(
+        &self,
+        attacker: &TeamMember,
+        mv: &CalcMove,
+        defender: &CalcPokemon,
+    ) -> Option<MatrixCell> {
+        let request = DamageCalcRequest {
+            attacker: attacker.pokemon.clone(),
+            defender: defender.clone(),
+            r#move: mv.clone(),
+            field: self.field,
+        };
+        let result = request.calculate()?;
+        Some(MatrixCell::new(
+            result.min,
+            result.max,
+            result.min_percent,
+            result.max_percent,
+            defender.hp(result.defender_hp),
+        ))
+    }
+}
+
+/// Damage of every move of one team against every member of another, in a
+/// single call.
+///
+/// `request` is `{ attackers, defenders, field }`, where each team member is
+/// `{ pokemon, moves }` with `pokemon` and `moves` as in
+/// `calculate_damage_request`.
+///
+/// Returns `cells[attacker][move][defender]` of `{ minPercent, maxPercent,
+/// ko, guaranteed }`, or null if the request is malformed.
+#[wasm_bindgen]
+pub fn calculate_damage_matrix(request: JsValue) -> JsValue {
+    match serde_wasm_bindgen::from_value::<DamageMatrixRequest>(request)
+        .ok()
+        .and_then(|r| r.calculate())
+    {
+        Some(cells) => serde_wasm_bindgen::to_value(&cells).unwrap_or(JsValue::NULL),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::MoveCategory;
+    use crate::test_utils::{attack, mon, mon_with_stats};
+    use pkmn_type_chart::PokemonType;
+
+    #[test]
+    fn test_matrix_shape_and_cells() {
+        let physical = |move_type| attack(move_type, 80, MoveCategory::Physical);
+        let request = DamageMatrixRequest {
+            attackers: vec![
+                TeamMember {
+                    pokemon: mon(&[PokemonType::Fire]),
+                    moves: vec![
+                        physical(PokemonType::Normal),
+                        physical(PokemonType::Fighting),
+                    ],
+                },
+                TeamMember {
+                    pokemon: mon(&[PokemonType::Water]),
+                    moves: vec![],
+                },
+            ],
+            defenders: vec![
+                TeamMember {
+                    pokemon: mon(&[PokemonType::Normal]),
+                    moves: vec![],
+                },
+                TeamMember {
+                    pokemon: mon_with_stats(&[PokemonType::Normal], [60, 100, 100, 100, 100, 100]),
+                    moves: vec![],
+                },
+                TeamMember {
+                    pokemon: mon(&[PokemonType::Ghost]),
+                    moves: vec![],
+                },
+            ],
+            field: CalcField::default(),
+        };
+        let cells = request.calculate().unwrap();
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells[0].len(), 2);
+        assert!(cells[1].is_empty());
+        assert_eq!(cells[0][0].len(), 3);
+
+        // 31-37 against 200 HP: never KOs within four hits
+        let cell = cells[0][0][0];
+        assert_eq!((cell.min_percent, cell.max_percent), (15.5, 18.5));
+        assert_eq!((cell.ko, cell.guaranteed), (0, false));
+        // 31-37 against 60 HP: a guaranteed 2HKO
+        assert_eq!((cells[0][0][1].ko, cells[0][0][1].guaranteed), (2, true));
+        // Ghost is immune to Normal
+        assert_eq!(cells[0][0][2].max_percent, 0.0);
+        // 62-74 Fighting against 60 HP: OHKO
+        assert_eq!((cells[0][1][1].ko, cells[0][1][1].guaranteed), (1, true));
+    }
+
+    #[test]
+    fn test_ko_class() {
+        assert_eq!(MatrixCell::new(45, 55, 0.0, 0.0, 100).ko, 2);
+        assert!(!MatrixCell::new(45, 55, 0.0, 0.0, 100).guaranteed);
+        assert_eq!(MatrixCell::new(0, 0, 0.0, 0.0, 100).ko, 0);
+        assert_eq!(MatrixCell::new(25, 25, 0.0, 0.0, 100).ko, 4);
+    }
+}
@@ -0,0 +1,330 @@
+use pkmn_stats::{Stat, WEATHER_HAIL, WEATHER_RAIN, WEATHER_SAND, WEATHER_SUN};
+use pkmn_type_chart::{normalize_name, PokemonType};
+use serde::{Deserialize, Serialize, Serializer};
+use wasm_bindgen::prelude::*;
+
+use crate::field::{TERRAIN_ELECTRIC, TERRAIN_GRASSY, TERRAIN_MISTY, TERRAIN_PSYCHIC};
+
+/// Highest Dynamax Level; the HP multiplier reaches 2x there.
+pub const MAX_DYNAMAX_LEVEL: u8 = 10;
+
+/// How a damaging move is used.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum MoveMode {
+    #[default]
+    Normal,
+    /// As the Max Move of a Dynamaxed Pokemon.
+    Max,
+    /// As the Z-Move powered up by a Z-Crystal.
+    Z,
+}
+
+/// Moves whose Max Move power is not given by the table, including every
+/// variable-power and fixed-damage move (see `variable_power` and
+/// `fixed_damage`) whose listed power is 0, and multi-hit moves, whose Max
+/// Move hits once.
+const MAX_POWER_EXCEPTIONS: [(&str, u32); 27] = [
+    ("lowkick", 100),
+    ("grassknot", 130),
+    ("heavyslam", 130),
+    ("heatcrash", 130),
+    ("gyroball", 130),
+    ("electroball", 130),
+    ("reversal", 100),
+    ("flail", 130),
+    ("seismictoss", 75),
+    ("nightshade", 100),
+    ("superfang", 100),
+    ("naturesmadness", 100),
+    ("endeavor", 130),
+    ("finalgambit", 75),
+    ("crushgrip", 140),
+    ("wringout", 140),
+    ("storedpower", 130),
+    ("powertrip", 130),
+    ("bulletseed", 130),
+    ("rockblast", 130),
+    ("iciclespear", 130),
+    ("pinmissile", 130),
+    ("tailslap", 130),
+    ("scaleshot", 130),
+    ("bonerush", 130),
+    ("doublekick", 80),
+    ("armthrust", 70),
+];
+
+/// Moves whose Z-Move power is not given by the table. Fixed-damage moves
+/// and weak multi-hit moves missing here (Super Fang, Night Shade, Double
+/// Kick, Arm Thrust, ...) get the table's 100.
+const Z_POWER_EXCEPTIONS: [(&str, u32); 29] = [
+    ("megadrain", 120),
+    ("weatherball", 160),
+    ("hex", 160),
+    ("geargrind", 180),
+    ("vcreate", 220),
+    ("flyingpress", 170),
+    ("coreenforcer", 140),
+    ("lowkick", 160),
+    ("grassknot", 160),
+    ("heavyslam", 160),
+    ("heatcrash", 160),
+    ("gyroball", 160),
+    ("electroball", 160),
+    ("reversal", 160),
+    ("flail", 160),
+    ("seismictoss", 100),
+    ("endeavor", 160),
+    ("finalgambit", 180),
+    ("crushgrip", 190),
+    ("wringout", 190),
+    ("storedpower", 160),
+    ("powertrip", 160),
+    ("bulletseed", 140),
+    ("rockblast", 140),
+    ("iciclespear", 140),
+    ("pinmissile", 140),
+    ("tailslap", 140),
+    ("scaleshot", 140),
+    ("bonerush", 140),
+];
+
+/// Base power of the Max Move a damaging move turns into.
+///
+/// Fighting and Poison moves (Max Knuckle, Max Ooze) use a lower table since
+/// their secondary effects boost the user's side.
+#[wasm_bindgen]
+pub fn max_move_power(move_name: &str, move_type: PokemonType, power: u32) -> u32 {
+    let name = normalize_name(move_name);
+    if let Some(&(_, power)) = MAX_POWER_EXCEPTIONS.iter().find(|(n, _)| *n == name) {
+        return power;
+    }
+    let weak = matches!(move_type, PokemonType::Fighting | PokemonType::Poison);
+    let (normal, reduced) = match power {
+        0..=40 => (90, 70),
+        41..=50 => (100, 75),
+        51..=60 => (110, 80),
+        61..=70 => (120, 85),
+        71..=100 => (130, 90),
+        101..=140 => (140, 95),
+        _ => (150, 100),
+    };
+    if weak {
+        reduced
+    } else {
+        normal
+    }
+}
+
+/// Base power of the Z-Move a damaging move turns into.
+#[wasm_bindgen]
+pub fn z_move_power(move_name: &str, power: u32) -> u32 {
+    let name = normalize_name(move_name);
+    if let Some(&(_, power)) = Z_POWER_EXCEPTIONS.iter().find(|(n, _)| *n == name) {
+        return power;
+    }
+    match power {
+        0..=55 => 100,
+        56..=65 => 120,
+        66..=75 => 140,
+        76..=85 => 160,
+        86..=95 => 175,
+        96..=100 => 180,
+        101..=110 => 185,
+        111..=125 => 190,
+        126..=130 => 195,
+        _ => 200,
+    }
+}
+
+/// Max HP while Dynamaxed: 1.5x at Dynamax Level 0 plus 0.05x per level,
+/// 2x at `MAX_DYNAMAX_LEVEL`.
+#[wasm_bindgen]
+pub fn dynamax_hp(hp: u32, dynamax_level: u8) -> u32 {
+    let level = dynamax_level.min(MAX_DYNAMAX_LEVEL) as u32;
+    hp * (150 + 5 * level) / 100
+}
+
+/// Secondary effect of a Max Move, by its type.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum MaxMoveEffect {
+    /// Sets the weather (see `CalcField::weather`).
+    Weather { weather: u8 },
+    /// Sets the terrain (see `CalcField::terrain`).
+    Terrain { terrain: u8 },
+    /// Raises the stat of the user and its partner by one stage.
+    StatBoost {
+        #[serde(serialize_with = "serialize_stat")]
+        stat: Stat,
+    },
+    /// Lowers the stat of the target and its partner by one stage.
+    StatDrop {
+        #[serde(serialize_with = "serialize_stat")]
+        stat: Stat,
+    },
+}
+
+fn serialize_stat<S: Serializer>(stat: &Stat, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(match stat {
+        Stat::Hp => "hp",
+        Stat::Atk => "attack",
+        Stat::Def => "defense",
+        Stat::SpA => "spAtk",
+        Stat::SpD => "spDef",
+        Stat::Spe => "speed",
+    })
+}
+
+/// Secondary effect of the Max Move of `move_type`.
+pub fn max_move_effect(move_type: PokemonType) -> MaxMoveEffect {
+    use MaxMoveEffect::*;
+    match move_type {
+        PokemonType::Normal => StatDrop { stat: Stat::Spe },
+        PokemonType::Fire => Weather {
+            weather: WEATHER_SUN,
+        },
+        PokemonType::Water => Weather {
+            weather: WEATHER_RAIN,
+        },
+        PokemonType::Electric => Terrain {
+            terrain: TERRAIN_ELECTRIC,
+        },
+        PokemonType::Grass => Terrain {
+            terrain: TERRAIN_GRASSY,
+        },
+        PokemonType::Ice => Weather {
+            weather: WEATHER_HAIL,
+        },
+        PokemonType::Fighting => StatBoost { stat: Stat::Atk },
+        PokemonType::Poison => StatBoost { stat: Stat::SpA },
+        PokemonType::Ground => StatBoost { stat: Stat::SpD },
+        PokemonType::Flying => StatBoost { stat: Stat::Spe },
+        PokemonType::Psychic => Terrain {
+            terrain: TERRAIN_PSYCHIC,
+        },
+        PokemonType::Bug => StatDrop { stat: Stat::SpA },
+        PokemonType::Rock => Weather {
+            weather: WEATHER_SAND,
+        },
+        PokemonType::Ghost => StatDrop { stat: Stat::Def },
+        PokemonType::Dragon => StatDrop { stat: Stat::Atk },
+        PokemonType::Dark => StatDrop { stat: Stat::SpD },
+        PokemonType::Steel => StatBoost { stat: Stat::Def },
+        PokemonType::Fairy => Terrain {
+            terrain: TERRAIN_MISTY,
+        },
+    }
+}
+
+/// Secondary effect of the Max Move of `move_type`, as
+/// `{ type: "weather", weather }`, `{ type: "terrain", terrain }`,
+/// `{ type: "statBoost", stat }` or `{ type: "statDrop", stat }`.
+#[wasm_bindgen]
+pub fn max_move_secondary_effect(move_type: PokemonType) -> JsValue {
+    serde_wasm_bindgen::to_value(&max_move_effect(move_type)).unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_move_power() {
+        assert_eq!(
+            max_move_power("Thunderbolt", PokemonType::Electric, 90),
+            130
+        );
+        assert_eq!(max_move_power("Flamethrower", PokemonType::Fire, 90), 130);
+        assert_eq!(max_move_power("Tackle", PokemonType::Normal, 40), 90);
+        assert_eq!(max_move_power("Earthquake", PokemonType::Ground, 100), 130);
+        assert_eq!(max_move_power("Outrage", PokemonType::Dragon, 120), 140);
+        assert_eq!(
+            max_move_power("Close Combat", PokemonType::Fighting, 120),
+            95
+        );
+        assert_eq!(max_move_power("Sludge Bomb", PokemonType::Poison, 90), 90);
+        assert_eq!(max_move_power("Low Kick", PokemonType::Fighting, 0), 100);
+        assert_eq!(max_move_power("Reversal", PokemonType::Fighting, 0), 100);
+        assert_eq!(max_move_power("Flail", PokemonType::Normal, 0), 130);
+        assert_eq!(max_move_power("Super Fang", PokemonType::Normal, 0), 100);
+        assert_eq!(max_move_power("Endeavor", PokemonType::Normal, 0), 130);
+        assert_eq!(max_move_power("Final Gambit", PokemonType::Fighting, 0), 75);
+        assert_eq!(max_move_power("Eruption", PokemonType::Fire, 150), 150);
+        assert_eq!(max_move_power("Crush Grip", PokemonType::Normal, 0), 140);
+        assert_eq!(
+            max_move_power("Stored Power", PokemonType::Psychic, 20),
+            130
+        );
+    }
+
+    #[test]
+    fn test_multi_hit_max_and_z_power() {
+        for name in [
+            "Bullet Seed",
+            "Rock Blast",
+            "Icicle Spear",
+            "Pin Missile",
+            "Tail Slap",
+        ] {
+            assert_eq!(max_move_power(name, PokemonType::Grass, 25), 130);
+            assert_eq!(z_move_power(name, 25), 140);
+        }
+        assert_eq!(max_move_power("Scale Shot", PokemonType::Dragon, 25), 130);
+        assert_eq!(max_move_power("Double Kick", PokemonType::Fighting, 30), 80);
+        assert_eq!(max_move_power("Arm Thrust", PokemonType::Fighting, 15), 70);
+        assert_eq!(z_move_power("Double Kick", 30), 100);
+    }
+
+    #[test]
+    fn test_z_move_power() {
+        assert_eq!(z_move_power("Thunderbolt", 90), 175);
+        assert_eq!(z_move_power("Earthquake", 100), 180);
+        assert_eq!(z_move_power("Close Combat", 120), 190);
+        assert_eq!(z_move_power("Brave Bird", 120), 190);
+        assert_eq!(z_move_power("Draco Meteor", 130), 195);
+        assert_eq!(z_move_power("Overheat", 130), 195);
+        assert_eq!(z_move_power("Quick Attack", 40), 100);
+        assert_eq!(z_move_power("V-create", 180), 220);
+        assert_eq!(z_move_power("Gear Grind", 50), 180);
+        for name in [
+            "Heavy Slam",
+            "Heat Crash",
+            "Electro Ball",
+            "Flail",
+            "Reversal",
+            "Endeavor",
+        ] {
+            assert_eq!(z_move_power(name, 0), 160);
+        }
+        assert_eq!(z_move_power("Final Gambit", 0), 180);
+        assert_eq!(z_move_power("Super Fang", 0), 100);
+        assert_eq!(z_move_power("Wring Out", 0), 190);
+        assert_eq!(z_move_power("Power Trip", 20), 160);
+    }
+
+    #[test]
+    fn test_dynamax_hp() {
+        assert_eq!(dynamax_hp(200, MAX_DYNAMAX_LEVEL), 400);
+        assert_eq!(dynamax_hp(200, 0), 300);
+        assert_eq!(dynamax_hp(201, 5), 351);
+    }
+
+    #[test]
+    fn test_max_move_effect() {
+        assert_eq!(
+            max_move_effect(PokemonType::Fire),
+            MaxMoveEffect::Weather {
+                weather: WEATHER_SUN
+            }
+        );
+        assert_eq!(
+            max_move_effect(PokemonType::Fighting),
+            MaxMoveEffect::StatBoost { stat: Stat::Atk }
+        );
+        assert_eq!(
+            max_move_effect(PokemonType::Dragon),
+            MaxMoveEffect::StatDrop { stat: Stat::Atk }
+        );
+    }
+}
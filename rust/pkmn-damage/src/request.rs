@@ -0,0 +1,1111 @@
+use pkmn_stats::{
+    apply_stat_stage, base_weather, calculate_stats, critical_hit_stage, fixed_damage,
+    variable_power, BattleStatModifiers, MoveContext, Nature, StatBlock, WEATHER_SUN,
+};
+use pkmn_type_chart::{
+    effectiveness_with_context, get_tera_stab_multiplier, normalize_name, tera_defensive_types,
+    EffectivenessContext, PokemonType, FIELD_GRAVITY, MOVE_FREEZE_DRY, MOVE_THOUSAND_ARROWS,
+};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::effects::{pinch_ability_type, resist_berry, type_boost_item};
+use crate::field::{
+    effective_weather, is_grounded, screen_modifier, terrain_modifier, weather_defense_modifier,
+    weather_modifier,
+};
+use crate::formula::{chain_modifiers, damage_rolls, modify, DamageModifiers, NUM_ROLLS};
+use crate::gen3::{gen3_critical_hit_chance, GEN3};
+use crate::ko_chance::{
+    critical_hit_chance, hit_distribution, ko_chance_from_distribution, residual_from_js, KoChance,
+    ResidualSources,
+};
+use crate::max_moves::{dynamax_hp, max_move_power, z_move_power, MoveMode, MAX_DYNAMAX_LEVEL};
+use crate::special_moves::{hit_counts, multi_hit_distribution};
+
+/// Major status condition.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum Status {
+    #[default]
+    None,
+    Burn,
+    Paralysis,
+    Poison,
+    Toxic,
+    Sleep,
+    Freeze,
+}
+
+impl Status {
+    pub fn is_statused(self) -> bool {
+        self != Status::None
+    }
+
+    pub fn is_poisoned(self) -> bool {
+        matches!(self, Status::Poison | Status::Toxic)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MoveCategory {
+    Physical,
+    Special,
+}
+
+/// Stat stages (-6 to +6). HP, accuracy and evasion stages are not tracked.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StatStages {
+    pub attack: i8,
+    pub defense: i8,
+    pub sp_atk: i8,
+    pub sp_def: i8,
+    pub speed: i8,
+}
+
+/// One side of a damage calculation. Every field is optional in JSON:
+/// level 50, 31 IVs, 0 EVs, Hardy nature, full HP and no ability or item.
+///
+/// Ability and item names are matched case-insensitively, ignoring spaces
+/// and hyphens (see `normalize_name`).
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CalcPokemon {
+    pub level: u32,
+    /// One or two types.
+    pub types: Vec<PokemonType>,
+    pub tera_type: Option<PokemonType>,
+    pub base_stats: StatBlock,
+    pub ivs: StatBlock,
+    pub evs: StatBlock,
+    /// Nature name ("adamant") or in-game index ("3"). Empty means Hardy.
+    pub nature: String,
+    /// Final stats. When set, `base_stats`, `ivs`, `evs` and `nature` are ignored.
+    pub stats: Option<StatBlock>,
+    pub boosts: StatStages,
+    pub ability: String,
+    pub item: String,
+    pub status: Status,
+    /// Current HP, or `None` for full HP.
+    pub current_hp: Option<u32>,
+    /// Weight in hectograms (0.1 kg), as in PokeAPI.
+    pub weight: u32,
+    /// Dynamaxed: max HP is doubled. `current_hp` is Dynamax HP too.
+    pub dynamax: bool,
+}
+
+impl Default for CalcPokemon {
+    fn default() -> Self {
+        CalcPokemon {
+            level: 50,
+            types: Vec::new(),
+            tera_type: None,
+            base_stats: StatBlock::default(),
+            ivs: StatBlock::uniform(31),
+            evs: StatBlock::default(),
+            nature: String::new(),
+            stats: None,
+            boosts: StatStages::default(),
+            ability: String::new(),
+            item: String::new(),
+            status: Status::None,
+            current_hp: None,
+            weight: 0,
+            dynamax: false,
+        }
+    }
+}
+
+impl CalcPokemon {
+    /// Final stats, with Dynamax HP if Dynamaxed, or `None` if the nature
+    /// is unknown.
+    pub fn final_stats(&self) -> Option<StatBlock> {
+        let mut stats = match self.stats {
+            Some(stats) => stats,
+            None => {
+                let nature = if self.nature.trim().is_empty() {
+                    Nature::default()
+                } else {
+                    self.nature.parse().ok()?
+                };
+                calculate_stats(&self.base_stats, &self.ivs, &self.evs, nature, self.level)
+            }
+        };
+        if self.dynamax {
+            stats.hp = dynamax_hp(stats.hp, MAX_DYNAMAX_LEVEL);
+        }
+        Some(stats)
+    }
+
+    /// Current HP, capped at `max_hp`.
+    pub fn hp(&self, max_hp: u32) -> u32 {
+        self.current_hp.unwrap_or(max_hp).min(max_hp)
+    }
+
+    /// Types as `(type1, type2)` with -1 for mono-type, or `None` without types.
+    fn type_indices(&self) -> Option<(u8, i8)> {
+        let type1 = self.types.first()?.index();
+        let type2 = self.types.get(1).map_or(-1, |t| t.index() as i8);
+        Some((type1, type2))
+    }
+
+    /// Types in battle: the tera type alone once Terastallized.
+    fn current_types(&self) -> Vec<PokemonType> {
+        match self.tera_type {
+            Some(tera) => vec![tera],
+            None => self.types.clone(),
+        }
+    }
+
+    /// Tera type index, or -1 when not Terastallized.
+    fn tera_index(&self) -> i8 {
+        self.tera_type.map_or(-1, |t| t.index() as i8)
+    }
+
+    /// Speed in battle after stages, items, abilities and paralysis.
+    pub(crate) fn battle_speed(&self, speed: u32, weather: u8) -> u32 {
+        BattleStatModifiers::new(
+            &self.ability,
+            &self.item,
+            weather,
+            self.status == Status::Paralysis,
+            self.status.is_statused(),
+            false,
+            false,
+        )
+        .speed(speed, self.boosts.speed)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CalcMove {
+    /// Move name, used for moves with special rules (Facade, Knock Off,
+    /// Foul Play, Body Press, Psyshock, ...).
+    #[serde(default)]
+    pub name: String,
+    #[serde(rename = "type")]
+    pub move_type: PokemonType,
+    pub power: u32,
+    pub category: MoveCategory,
+    #[serde(default)]
+    pub critical: bool,
+    /// The move's own critical hit stage (1 for Stone Edge, Slash, ...).
+    #[serde(default)]
+    pub crit_stage: u8,
+    /// Hit range of a multi-hit move (2 and 5 for Bullet Seed). 0 or 1 means a single hit.
+    #[serde(default)]
+    pub min_hits: u32,
+    #[serde(default)]
+    pub max_hits: u32,
+    /// Used as a Max Move or Z-Move: base power is converted, the move hits
+    /// once and its name-based effects are dropped.
+    #[serde(default)]
+    pub mode: MoveMode,
+}
+
+impl Default for CalcMove {
+    fn default() -> Self {
+        CalcMove {
+            name: String::new(),
+            move_type: PokemonType::Normal,
+            power: 0,
+            category: MoveCategory::Physical,
+            critical: false,
+            crit_stage: 0,
+            min_hits: 0,
+            max_hits: 0,
+            mode: MoveMode::Normal,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CalcField {
+    /// 0 = none, 1 = sun, 2 = rain, 3 = sandstorm, 4 = hail, 5 = snow,
+    /// 6 = harsh sunlight, 7 = heavy rain.
+    pub weather: u8,
+    /// 0 = none, 1 = Electric, 2 = Grassy, 3 = Psychic, 4 = Misty.
+    pub terrain: u8,
+    pub gravity: bool,
+    /// Screens on the defender's side.
+    pub reflect: bool,
+    pub light_screen: bool,
+    pub aurora_veil: bool,
+    /// Double battle: screens reduce damage by 2732/4096 instead of half.
+    pub doubles: bool,
+    /// The move hits more than one target (0.75x). In Gen 3 this is 0.5x and
+    /// only set for moves that hit both foes (see `Gen3Modifiers::spread`).
+    pub spread: bool,
+    /// The attacker's partner used Helping Hand (1.5x power).
+    pub helping_hand: bool,
+    /// The attacker's partner has Battery (1.3x special power) or Power
+    /// Spot (1.3x power).
+    pub battery: bool,
+    pub power_spot: bool,
+    /// Flower Gift on the attacker's side (1.5x Attack in sun).
+    pub attacker_flower_gift: bool,
+    /// Flower Gift on the defender's side (1.5x Sp. Def in sun).
+    pub defender_flower_gift: bool,
+    /// The defender's partner has Friend Guard (0.75x).
+    pub friend_guard: bool,
+    /// The defender used Protect: Max Moves and Z-Moves hit for 0.25x,
+    /// other moves deal no damage.
+    pub protect: bool,
+    /// 3 selects Gen 3 mechanics (see `DamageCalcRequest::calculate`); any
+    /// other value uses the current ones.
+    pub generation: u8,
+}
+
+/// A complete damage calculation: who attacks whom, with what, where.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DamageCalcRequest {
+    pub attacker: CalcPokemon,
+    pub defender: CalcPokemon,
+    pub r#move: CalcMove,
+    #[serde(default)]
+    pub field: CalcField,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DamageCalcResult {
+    /// All 16 rolls of a single hit, lowest to highest.
+    pub rolls: Vec<u32>,
+    /// Total damage of the move: the lowest roll at the fewest hits and the
+    /// highest roll at the most hits.
+    pub min: u32,
+    pub max: u32,
+    pub min_hits: u32,
+    pub max_hits: u32,
+    /// `min` and `max` as a percentage of the defender's max HP.
+    pub min_percent: f64,
+    pub max_percent: f64,
+    pub defender_hp: u32,
+    pub effectiveness: f64,
+    /// Why the move dealt no damage (see `ImmunityReason`), 0 if it was not blocked.
+    pub immunity: u8,
+    pub stab: f64,
+    /// Base power, attacking and defending stats after every modifier.
+    pub power: u32,
+    pub attack: u32,
+    pub defense: u32,
+}
+
+impl DamageCalcResult {
+    /// Fill in the totals from `rolls` (one hit) and the move's hit counts.
+    pub(crate) fn apply_hit_counts(&mut self, counts: &[(u32, f64)]) {
+        self.min_hits = counts.first().map_or(1, |&(hits, _)| hits);
+        self.max_hits = counts.last().map_or(1, |&(hits, _)| hits);
+        self.min = self.rolls.first().copied().unwrap_or(0) * self.min_hits;
+        self.max = self.rolls.last().copied().unwrap_or(0) * self.max_hits;
+        let percent = |damage: u32| {
+            if self.defender_hp == 0 {
+                0.0
+            } else {
+                damage as f64 * 100.0 / self.defender_hp as f64
+            }
+        };
+        self.min_percent = percent(self.min);
+        self.max_percent = percent(self.max);
+    }
+}
+
+impl DamageCalcRequest {
+    /// Resolve every ability, item and move effect and compute the damage rolls.
+    ///
+    /// With `field.generation` 3, Gen 3 rules apply instead: the move's type
+    /// decides physical or special, critical hits deal 2x, there is no Fairy
+    /// type and the modifiers follow the Gen 3 order.
+    ///
+    /// Returns `None` if either Pokemon has no types or an unknown nature.
+    pub fn calculate(&self) -> Option<DamageCalcResult> {
+        if self.field.generation == GEN3 {
+            return self.calculate_gen3();
+        }
+        let (attacker, defender, mv) = (&self.attacker, &self.defender, &self.r#move);
+        let atk_stats = attacker.final_stats()?;
+        let def_stats = defender.final_stats()?;
+        let (atk_type1, atk_type2) = attacker.type_indices()?;
+        let (def_type1, def_type2) = defender.type_indices()?;
+
+        let atk_ability = normalize_name(&attacker.ability);
+        let atk_item = normalize_name(&attacker.item);
+        let ignores_ability = matches!(
+            atk_ability.as_str(),
+            "moldbreaker" | "teravolt" | "turboblaze"
+        );
+        let def_ability = if ignores_ability {
+            String::new()
+        } else {
+            normalize_name(&defender.ability)
+        };
+        let def_item = normalize_name(&defender.item);
+        // Max Moves and Z-Moves lose the special rules of the base move
+        let move_name = match mv.mode {
+            MoveMode::Normal => normalize_name(&mv.name),
+            MoveMode::Max | MoveMode::Z => String::new(),
+        };
+        let weather = effective_weather(
+            self.field.weather,
+            &[&atk_ability, &normalize_name(&defender.ability)],
+        );
+        let atk_types = attacker.current_types();
+        let def_types = defender.current_types();
+
+        let physical = mv.category == MoveCategory::Physical;
+        let targets_defense =
+            physical || matches!(move_name.as_str(), "psyshock" | "psystrike" | "secretsword");
+        let critical = mv.critical;
+        let move_type = mv.move_type;
+        let attacker_hp = attacker.hp(atk_stats.hp);
+        let defender_hp = defender.hp(def_stats.hp);
+        let move_ctx = MoveContext {
+            attacker_level: attacker.level,
+            attacker_hp,
+            attacker_max_hp: atk_stats.hp,
+            defender_hp,
+            defender_max_hp: def_stats.hp,
+            attacker_speed: attacker.battle_speed(atk_stats.speed, weather),
+            defender_speed: defender.battle_speed(def_stats.speed, weather),
+            attacker_weight: attacker.weight,
+            defender_weight: defender.weight,
+        };
+
+        // Type effectiveness
+        let (def_type1, def_type2) =
+            tera_defensive_types(def_type1, def_type2, defender.tera_index());
+        let move_flags = match move_name.as_str() {
+            "freezedry" => MOVE_FREEZE_DRY,
+            "thousandarrows" => MOVE_THOUSAND_ARROWS,
+            _ => 0,
+        };
+        let ctx = EffectivenessContext {
+            attacker_ability: &atk_ability,
+            defender_ability: &def_ability,
+            defender_item: &def_item,
+            move_flags,
+            field_flags: if self.field.gravity { FIELD_GRAVITY } else { 0 },
+        };
+        let effectiveness =
+            effectiveness_with_context(move_type.index(), def_type1, def_type2, &ctx);
+        let super_effective = effectiveness.multiplier > 1.0;
+
+        // Base power
+        let doubled = match move_name.as_str() {
+            "facade" => attacker.status.is_statused() && attacker.status != Status::Sleep,
+            "acrobatics" => atk_item.is_empty(),
+            "hex" => defender.status.is_statused(),
+            "venoshock" => defender.status.is_poisoned(),
+            "brine" => defender_hp * 2 <= def_stats.hp,
+            _ => false,
+        };
+        let base_power = match mv.mode {
+            MoveMode::Normal => variable_power(&move_name, &move_ctx).unwrap_or(mv.power),
+            MoveMode::Max => max_move_power(&mv.name, move_type, mv.power),
+            MoveMode::Z => z_move_power(&mv.name, mv.power),
+        };
+        let base_power = if doubled { base_power * 2 } else { base_power };
+        let mut power_mods = Vec::new();
+        if atk_ability == "technician" && base_power <= 60 {
+            power_mods.push(1.5);
+        }
+        if type_boost_item(&atk_item) == Some(move_type) {
+            power_mods.push(4915.0 / 4096.0);
+        }
+        match (atk_item.as_str(), physical) {
+            ("muscleband", true) | ("wiseglasses", false) => power_mods.push(4505.0 / 4096.0),
+            _ => {}
+        }
+        if move_name == "knockoff" && !def_item.is_empty() {
+            power_mods.push(1.5);
+        }
+        if self.field.helping_hand {
+            power_mods.push(1.5);
+        }
+        if self.field.power_spot {
+            power_mods.push(5325.0 / 4096.0);
+        }
+        if self.field.battery && !physical {
+            power_mods.push(5325.0 / 4096.0);
+        }
+        power_mods.push(terrain_modifier(
+            self.field.terrain,
+            move_type,
+            &move_name,
+            is_grounded(&atk_types, &atk_ability, &atk_item, self.field.gravity),
+            is_grounded(&def_types, &def_ability, &def_item, self.field.gravity),
+        ));
+        let power = if base_power == 0 {
+            0
+        } else {
+            (modify(base_power as u64, chain_modifiers(&power_mods)) as u32).max(1)
+        };
+
+        // Attacking stat
+        let (atk_source, atk_stage) = match move_name.as_str() {
+            "foulplay" => (def_stats.attack, defender.boosts.attack),
+            "bodypress" => (atk_stats.defense, attacker.boosts.defense),
+            _ if physical => (atk_stats.attack, attacker.boosts.attack),
+            _ => (atk_stats.sp_atk, attacker.boosts.sp_atk),
+        };
+        let atk_stage = if def_ability == "unaware" {
+            0
+        } else if critical {
+            critical_hit_stage(atk_stage, true)
+        } else {
+            atk_stage
+        };
+        // Choice items do nothing while Dynamaxed
+        let choice_item = if move_name == "bodypress" || mv.mode == MoveMode::Max {
+            ""
+        } else {
+            &attacker.item
+        };
+        let stat_mods = BattleStatModifiers::new(
+            &attacker.ability,
+            choice_item,
+            weather,
+            false,
+            false,
+            false,
+            false,
+        );
+        let sun = base_weather(weather) == WEATHER_SUN;
+        let pinch =
+            pinch_ability_type(&atk_ability) == Some(move_type) && attacker_hp * 3 <= atk_stats.hp;
+        // Body Press uses Defense, so Attack-only boosts do not apply
+        let uses_attack = physical && move_name != "bodypress";
+        let mut atk_mods = Vec::new();
+        match atk_ability.as_str() {
+            "hugepower" | "purepower" if uses_attack => atk_mods.push(2.0),
+            "guts" if uses_attack && attacker.status.is_statused() => atk_mods.push(1.5),
+            "hustle" if uses_attack => atk_mods.push(1.5),
+            "solarpower" if !physical && sun => atk_mods.push(1.5),
+            _ if pinch => atk_mods.push(1.5),
+            _ => {}
+        }
+        if self.field.attacker_flower_gift && uses_attack && sun {
+            atk_mods.push(1.5);
+        }
+        match (def_ability.as_str(), move_type) {
+            ("thickfat", PokemonType::Fire | PokemonType::Ice)
+            | ("heatproof" | "waterbubble", PokemonType::Fire) => atk_mods.push(0.5),
+            _ => {}
+        }
+        let attack = modify(
+            stat_mods.attack(atk_source, atk_stage, physical) as u64,
+            chain_modifiers(&atk_mods),
+        ) as u32;
+
+        // Defending stat
+        let (def_source, def_stage) = if targets_defense {
+            (def_stats.defense, defender.boosts.defense)
+        } else {
+            (def_stats.sp_def, defender.boosts.sp_def)
+        };
+        let def_stage = if atk_ability == "unaware" {
+            0
+        } else if critical {
+            critical_hit_stage(def_stage, false)
+        } else {
+            def_stage
+        };
+        let mut def_mods = vec![weather_defense_modifier(
+            weather,
+            &def_types,
+            targets_defense,
+        )];
+        if def_item == "eviolite" || (def_item == "assaultvest" && !targets_defense) {
+            def_mods.push(1.5);
+        }
+        match def_ability.as_str() {
+            "furcoat" if targets_defense => def_mods.push(2.0),
+            "marvelscale" if targets_defense && defender.status.is_statused() => def_mods.push(1.5),
+            _ => {}
+        }
+        if self.field.defender_flower_gift && !targets_defense && sun && !ignores_ability {
+            def_mods.push(1.5);
+        }
+        let defense = modify(
+            apply_stat_stage(def_source, def_stage) as u64,
+            chain_modifiers(&def_mods),
+        ) as u32;
+
+        // Final modifiers
+        let weather_mult = weather_modifier(weather, move_type);
+        let stab = get_tera_stab_multiplier(
+            move_type.index(),
+            atk_type1,
+            atk_type2,
+            attacker.tera_index(),
+            atk_ability == "adaptability",
+            false,
+        );
+        let burned = attacker.status == Status::Burn
+            && physical
+            && atk_ability != "guts"
+            && move_name != "facade";
+
+        let mut other = Vec::new();
+        if !critical && atk_ability != "infiltrator" {
+            other.push(screen_modifier(
+                physical,
+                self.field.reflect,
+                self.field.light_screen,
+                self.field.aurora_veil,
+                self.field.doubles,
+            ));
+        }
+        if matches!(def_ability.as_str(), "multiscale" | "shadowshield")
+            && defender_hp == def_stats.hp
+        {
+            other.push(0.5);
+        }
+        if matches!(def_ability.as_str(), "filter" | "solidrock" | "prismarmor") && super_effective
+        {
+            other.push(0.75);
+        }
+        if atk_ability == "tintedlens" && effectiveness.multiplier < 1.0 {
+            other.push(2.0);
+        }
+        if atk_ability == "sniper" && critical {
+            other.push(1.5);
+        }
+        if def_ability == "icescales" && !physical {
+            other.push(0.5);
+        }
+        if self.field.friend_guard && !ignores_ability {
+            other.push(0.75);
+        }
+        if atk_item == "expertbelt" && super_effective {
+            other.push(4915.0 / 4096.0);
+        }
+        if atk_item == "lifeorb" {
+            other.push(5324.0 / 4096.0);
+        }
+        if resist_berry(&def_item) == Some(move_type)
+            && (super_effective || move_type == PokemonType::Normal)
+        {
+            other.push(0.5);
+        }
+
+        let mods = DamageModifiers {
+            spread: self.field.spread,
+            weather: weather_mult,
+            critical,
+            stab,
+            effectiveness: effectiveness.multiplier,
+            burned,
+            other: chain_modifiers(&other),
+            z_move_into_protect: self.field.protect,
+            ..Default::default()
+        };
+        let protected = self.field.protect && mv.mode == MoveMode::Normal;
+        let rolls = match fixed_damage(&move_name, &move_ctx) {
+            _ if protected => [0; NUM_ROLLS],
+            Some(damage) if effectiveness.multiplier > 0.0 => [damage; NUM_ROLLS],
+            Some(_) => [0; NUM_ROLLS],
+            // Water moves in harsh sunlight and Fire moves in heavy rain fail
+            None if weather_mult == 0.0 => [0; NUM_ROLLS],
+            None => damage_rolls(attacker.level, power, attack, defense, &mods),
+        };
+        let mut result = DamageCalcResult {
+            rolls: rolls.to_vec(),
+            defender_hp: def_stats.hp,
+            effectiveness: effectiveness.multiplier,
+            immunity: effectiveness.reason as u8,
+            stab,
+            power,
+            attack,
+            defense,
+            ..Default::default()
+        };
+        result.apply_hit_counts(&self.move_hit_counts());
+        Some(result)
+    }
+
+    /// Exact nHKO chance of the move (see `ko_chance`), over every roll,
+    /// critical hit and number of hits.
+    ///
+    /// Empty `types`, `ability` and `item` in `residual` fall back to the
+    /// defender's own. Returns `None` when `calculate` does.
+    pub fn ko_chance(&self, residual: &ResidualSources) -> Option<KoChance> {
+        let regular = self.calculate()?;
+        let mut crit_request = self.clone();
+        crit_request.r#move.critical = true;
+        let crit = crit_request.calculate()?;
+
+        let per_hit = hit_distribution(&regular.rolls, &crit.rolls, self.crit_chance());
+        let total = multi_hit_distribution(&per_hit, &self.move_hit_counts());
+
+        let mut residual = residual.clone();
+        if residual.types.is_empty() {
+            residual.types = self.defender.types.clone();
+        }
+        if residual.ability.is_empty() {
+            residual.ability = self.defender.ability.clone();
+        }
+        if residual.item.is_empty() {
+            residual.item = self.defender.item.clone();
+        }
+        let max_hp = regular.defender_hp;
+        Some(ko_chance_from_distribution(
+            &total,
+            max_hp,
+            self.defender.hp(max_hp),
+            &residual,
+        ))
+    }
+
+    pub(crate) fn move_hit_counts(&self) -> Vec<(u32, f64)> {
+        if self.r#move.mode != MoveMode::Normal {
+            return vec![(1, 1.0)];
+        }
+        hit_counts(
+            &self.r#move.name,
+            self.r#move.min_hits,
+            self.r#move.max_hits,
+            normalize_name(&self.attacker.ability) == "skilllink",
+            normalize_name(&self.attacker.item) == "loadeddice",
+        )
+    }
+
+    /// Chance of a critical hit: the move's stage plus Super Luck and Scope
+    /// Lens / Razor Claw. Battle Armor and Shell Armor prevent crits.
+    fn crit_chance(&self) -> f64 {
+        if self.r#move.critical {
+            return 1.0;
+        }
+        let defender_ability = normalize_name(&self.defender.ability);
+        if matches!(defender_ability.as_str(), "battlearmor" | "shellarmor") {
+            return 0.0;
+        }
+        let mut stage = self.r#move.crit_stage;
+        if normalize_name(&self.attacker.ability) == "superluck" {
+            stage += 1;
+        }
+        if matches!(
+            normalize_name(&self.attacker.item).as_str(),
+            "scopelens" | "razorclaw"
+        ) {
+            stage += 1;
+        }
+        if self.field.generation == GEN3 {
+            gen3_critical_hit_chance(stage)
+        } else {
+            critical_hit_chance(stage)
+        }
+    }
+}
+
+/// Calculate damage from a structured request, resolving items, abilities
+/// and move effects in Rust.
+///
+/// `request` is `{ attacker, defender, move, field }`:
+/// - `attacker` / `defender`: `{ level, types, teraType, baseStats, ivs, evs,
+///   nature, stats, boosts, ability, item, status, currentHp, weight, dynamax }`, all
+///   optional except `types`. `stats` (final stats) overrides the stat calculation.
+/// - `move`: `{ name, type, power, category: "physical" | "special", critical,
+///   critStage, minHits, maxHits, mode: "normal" | "max" | "z" }`
+/// - `field`: `{ weather, terrain, gravity, reflect, lightScreen, auroraVeil,
+///   doubles, spread, helpingHand, battery, powerSpot, attackerFlowerGift,
+///   defenderFlowerGift, friendGuard, protect, generation }` (optional)
+///
+/// Fixed-damage moves (Seismic Toss, Super Fang, ...) and variable-power
+/// moves (Low Kick, Gyro Ball, Eruption, ...) are recognised by name.
+///
+/// Returns `{ rolls, min, max, minHits, maxHits, minPercent, maxPercent, defenderHp,
+/// effectiveness, immunity, stab, power, attack, defense }`, or null if the
+/// request is malformed.
+#[wasm_bindgen]
+pub fn calculate_damage_request(request: JsValue) -> JsValue {
+    match serde_wasm_bindgen::from_value::<DamageCalcRequest>(request)
+        .ok()
+        .and_then(|r| r.calculate())
+    {
+        Some(result) => serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL),
+        None => JsValue::NULL,
+    }
+}
+
+/// KO chance of a structured request (see `calculate_damage_request`),
+/// including critical hits, multi-hit distributions and residual damage.
+///
+/// `residual` is `{ stealthRock, weather, types, ability, item }` (optional;
+/// types, ability and item default to the defender's).
+///
+/// Returns `{ chance, hits, text }`, or null if either argument is malformed.
+#[wasm_bindgen]
+pub fn calculate_ko_chance_request(request: JsValue, residual: JsValue) -> JsValue {
+    let Some(residual) = residual_from_js(residual) else {
+        return JsValue::NULL;
+    };
+    match serde_wasm_bindgen::from_value::<DamageCalcRequest>(request)
+        .ok()
+        .and_then(|r| r.ko_chance(&residual))
+    {
+        Some(ko) => serde_wasm_bindgen::to_value(&ko).unwrap_or(JsValue::NULL),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::{TERRAIN_ELECTRIC, TERRAIN_GRASSY};
+    use crate::test_utils::{attack, mon, request};
+    use pkmn_stats::{
+        WEATHER_HAIL, WEATHER_HARSH_SUN, WEATHER_HEAVY_RAIN, WEATHER_SAND, WEATHER_SNOW,
+    };
+    use pkmn_type_chart::ImmunityReason;
+
+    fn normal_hit() -> DamageCalcRequest {
+        request(attack(PokemonType::Normal, 80, MoveCategory::Physical))
+    }
+
+    #[test]
+    fn test_plain_hit_matches_formula() {
+        let result = normal_hit().calculate().unwrap();
+        let expected = damage_rolls(50, 80, 100, 100, &DamageModifiers::default());
+        assert_eq!(result.rolls, expected.to_vec());
+        assert_eq!((result.min, result.max), (31, 37));
+        assert_eq!(result.max_percent, 18.5);
+        assert_eq!(result.defender_hp, 200);
+    }
+
+    #[test]
+    fn test_stats_from_spread() {
+        // Adamant 252 Atk Garchomp at level 50: 200 Attack, 300 with Choice Band
+        let mut req = normal_hit();
+        req.attacker = CalcPokemon {
+            types: vec![PokemonType::Dragon, PokemonType::Ground],
+            base_stats: StatBlock::from_array([108, 130, 95, 80, 85, 102]),
+            evs: StatBlock::from_array([0, 252, 0, 0, 4, 252]),
+            nature: "Adamant".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(req.calculate().unwrap().attack, 200);
+        req.attacker.item = "Choice Band".to_string();
+        assert_eq!(req.calculate().unwrap().attack, 300);
+        req.attacker.nature = "bogus".to_string();
+        assert!(req.calculate().is_none());
+    }
+
+    #[test]
+    fn test_attacker_items() {
+        let mut req = normal_hit();
+        req.attacker.item = "Life Orb".to_string();
+        assert_eq!(req.calculate().unwrap().max, 48);
+
+        req.attacker.item = "Silk Scarf".to_string();
+        // 80 * 4915 / 4096 = 95.99 -> 96
+        assert_eq!(req.calculate().unwrap().power, 96);
+        req.attacker.item = "Charcoal".to_string();
+        assert_eq!(req.calculate().unwrap().power, 80);
+
+        req.r#move.move_type = PokemonType::Fighting;
+        let neutral = req.calculate().unwrap().max;
+        req.attacker.item = "Expert Belt".to_string();
+        assert!(req.calculate().unwrap().max > neutral);
+    }
+
+    #[test]
+    fn test_attacker_abilities() {
+        let mut req = normal_hit();
+        req.attacker.ability = "Huge Power".to_string();
+        assert_eq!(req.calculate().unwrap().attack, 200);
+
+        req.attacker.ability = "Technician".to_string();
+        req.r#move.power = 60;
+        assert_eq!(req.calculate().unwrap().power, 90);
+        req.r#move.power = 65;
+        assert_eq!(req.calculate().unwrap().power, 65);
+
+        req.attacker.ability = "Adaptability".to_string();
+        req.r#move.move_type = PokemonType::Fire;
+        assert_eq!(req.calculate().unwrap().stab, 2.0);
+        req.attacker.ability.clear();
+        req.attacker.tera_type = Some(PokemonType::Fire);
+        assert_eq!(req.calculate().unwrap().stab, 2.0);
+
+        // Blaze at 1/3 HP
+        req.attacker.ability = "Blaze".to_string();
+        assert_eq!(req.calculate().unwrap().attack, 100);
+        req.attacker.current_hp = Some(66);
+        assert_eq!(req.calculate().unwrap().attack, 150);
+    }
+
+    #[test]
+    fn test_burn_and_guts() {
+        let mut req = normal_hit();
+        req.attacker.status = Status::Burn;
+        assert_eq!(req.calculate().unwrap().max, 18);
+        req.attacker.ability = "Guts".to_string();
+        let guts = req.calculate().unwrap();
+        assert_eq!(guts.attack, 150);
+        assert!(guts.max > 37);
+
+        req.attacker.ability.clear();
+        req.r#move.name = "Facade".to_string();
+        req.r#move.power = 70;
+        let facade = req.calculate().unwrap();
+        assert_eq!(facade.power, 140);
+        assert!(facade.max > 37);
+    }
+
+    #[test]
+    fn test_defender_effects() {
+        let mut req = request(attack(PokemonType::Fighting, 80, MoveCategory::Physical));
+        let full = req.calculate().unwrap().max;
+
+        req.defender.ability = "Multiscale".to_string();
+        assert_eq!(req.calculate().unwrap().max, full / 2);
+        req.defender.current_hp = Some(199);
+        assert_eq!(req.calculate().unwrap().max, full);
+
+        req.defender.ability = "Filter".to_string();
+        assert_eq!(req.calculate().unwrap().max, full * 3 / 4);
+        req.attacker.ability = "Mold Breaker".to_string();
+        assert_eq!(req.calculate().unwrap().max, full);
+
+        req.attacker.ability.clear();
+        req.defender.ability.clear();
+        req.defender.item = "Chople Berry".to_string();
+        assert_eq!(req.calculate().unwrap().max, full / 2);
+
+        req.defender.item = "Assault Vest".to_string();
+        assert_eq!(req.calculate().unwrap().defense, 100);
+        req.r#move.category = MoveCategory::Special;
+        assert_eq!(req.calculate().unwrap().defense, 150);
+    }
+
+    #[test]
+    fn test_immunities() {
+        let mut req = request(attack(PokemonType::Ground, 100, MoveCategory::Physical));
+        req.defender.ability = "Levitate".to_string();
+        let result = req.calculate().unwrap();
+        assert_eq!(result.max, 0);
+        assert_eq!(result.immunity, ImmunityReason::Levitate as u8);
+
+        req.r#move = attack(PokemonType::Normal, 80, MoveCategory::Physical);
+        req.defender.types = vec![PokemonType::Ghost];
+        assert_eq!(
+            req.calculate().unwrap().immunity,
+            ImmunityReason::Type as u8
+        );
+        req.attacker.ability = "Scrappy".to_string();
+        assert_eq!(req.calculate().unwrap().max, 37);
+
+        req.defender.types.clear();
+        assert!(req.calculate().is_none());
+    }
+
+    #[test]
+    fn test_move_effects() {
+        let mut req = normal_hit();
+        req.defender.stats = Some(StatBlock::from_array([200, 150, 100, 100, 50, 100]));
+
+        req.r#move.name = "Foul Play".to_string();
+        assert_eq!(req.calculate().unwrap().attack, 150);
+
+        req.attacker.stats = Some(StatBlock::from_array([200, 100, 120, 100, 100, 100]));
+        req.r#move = attack(PokemonType::Fighting, 80, MoveCategory::Physical);
+        req.r#move.name = "Body Press".to_string();
+        assert_eq!(req.calculate().unwrap().attack, 120);
+        for (ability, item) in [("Huge Power", ""), ("Hustle", ""), ("Guts", "Choice Band")] {
+            req.attacker.ability = ability.to_string();
+            req.attacker.item = item.to_string();
+            req.attacker.status = Status::Burn;
+            assert_eq!(req.calculate().unwrap().attack, 120);
+        }
+        req.attacker = mon(&[PokemonType::Fire]);
+
+        req.r#move = attack(PokemonType::Psychic, 80, MoveCategory::Special);
+        assert_eq!(req.calculate().unwrap().defense, 50);
+        req.r#move.name = "Psyshock".to_string();
+        assert_eq!(req.calculate().unwrap().defense, 100);
+
+        req.r#move = attack(PokemonType::Dark, 65, MoveCategory::Physical);
+        req.r#move.name = "Knock Off".to_string();
+        assert_eq!(req.calculate().unwrap().power, 65);
+        req.defender.item = "Leftovers".to_string();
+        assert_eq!(req.calculate().unwrap().power, 97);
+    }
+
+    #[test]
+    fn test_crit_ignores_stages() {
+        let mut req = normal_hit();
+        req.attacker.boosts.attack = -2;
+        req.defender.boosts.defense = 2;
+        let plain = req.calculate().unwrap();
+        assert_eq!((plain.attack, plain.defense), (50, 200));
+
+        req.r#move.critical = true;
+        let crit = req.calculate().unwrap();
+        assert_eq!((crit.attack, crit.defense), (100, 100));
+        assert_eq!(crit.max, 55);
+    }
+
+    #[test]
+    fn test_field_effects() {
+        let mut req = normal_hit();
+        req.field.reflect = true;
+        assert_eq!(req.calculate().unwrap().max, 18);
+        req.field.doubles = true;
+        assert_eq!(req.calculate().unwrap().max, 25);
+        req.r#move.critical = true;
+        assert_eq!(req.calculate().unwrap().max, 55);
+        req.r#move.critical = false;
+        req.attacker.ability = "Infiltrator".to_string();
+        assert_eq!(req.calculate().unwrap().max, 37);
+
+        // Psyshock hits Defense but is still special: Light Screen applies
+        let mut req = request(attack(PokemonType::Psychic, 80, MoveCategory::Special));
+        req.r#move.name = "Psyshock".to_string();
+        req.field.reflect = true;
+        assert_eq!(req.calculate().unwrap().max, 37);
+        req.field.reflect = false;
+        req.field.light_screen = true;
+        assert_eq!(req.calculate().unwrap().max, 18);
+
+        let mut req = request(attack(PokemonType::Electric, 80, MoveCategory::Special));
+        req.field.terrain = TERRAIN_ELECTRIC;
+        assert_eq!(req.calculate().unwrap().power, 104);
+        req.attacker.types = vec![PokemonType::Flying];
+        assert_eq!(req.calculate().unwrap().power, 80);
+        req.field.gravity = true;
+        assert_eq!(req.calculate().unwrap().power, 104);
+
+        req.r#move = attack(PokemonType::Ground, 100, MoveCategory::Physical);
+        req.r#move.name = "Earthquake".to_string();
+        req.field.terrain = TERRAIN_GRASSY;
+        assert_eq!(req.calculate().unwrap().power, 50);
+    }
+
+    #[test]
+    fn test_weather_effects() {
+        let mut req = request(attack(PokemonType::Water, 80, MoveCategory::Special));
+        req.field.weather = WEATHER_HARSH_SUN;
+        assert_eq!(req.calculate().unwrap().max, 0);
+        req.attacker.ability = "Cloud Nine".to_string();
+        assert_eq!(req.calculate().unwrap().max, 37);
+        req.attacker.ability.clear();
+        req.field.weather = WEATHER_HEAVY_RAIN;
+        assert_eq!(req.calculate().unwrap().max, 55);
+
+        req.defender.types = vec![PokemonType::Rock];
+        req.field.weather = WEATHER_SAND;
+        assert_eq!(req.calculate().unwrap().defense, 150);
+        req.defender.tera_type = Some(PokemonType::Steel);
+        assert_eq!(req.calculate().unwrap().defense, 100);
+
+        req.r#move.category = MoveCategory::Physical;
+        req.defender.types = vec![PokemonType::Ice];
+        req.defender.tera_type = None;
+        req.field.weather = WEATHER_SNOW;
+        assert_eq!(req.calculate().unwrap().defense, 150);
+        req.field.weather = WEATHER_HAIL;
+        assert_eq!(req.calculate().unwrap().defense, 100);
+    }
+
+    #[test]
+    fn test_max_and_z_moves() {
+        let mut req = normal_hit();
+        req.r#move.name = "Facade".to_string();
+        req.r#move.power = 70;
+        req.r#move.min_hits = 2;
+        req.r#move.max_hits = 5;
+        req.attacker.status = Status::Paralysis;
+        req.attacker.item = "Choice Band".to_string();
+        req.r#move.mode = MoveMode::Max;
+        let max = req.calculate().unwrap();
+        // Max Strike: 120 power, Facade's doubling and Choice Band dropped, one hit
+        assert_eq!((max.power, max.attack, max.max_hits), (120, 100, 1));
+
+        // A Z-Move needs the matching Z-Crystal as the held item
+        req.attacker.item = "Normalium Z".to_string();
+        req.r#move.mode = MoveMode::Z;
+        let z = req.calculate().unwrap();
+        assert_eq!((z.power, z.attack, z.max_hits), (140, 100, 1));
+
+        // Z-Moves hit through Protect at 0.25x, regular moves do not
+        req.field.protect = true;
+        assert!(req.calculate().unwrap().max > 0);
+        req.r#move.mode = MoveMode::Normal;
+        assert_eq!(req.calculate().unwrap().max, 0);
+    }
+
+    #[test]
+    fn test_dynamax_hp() {
+        let mut req = normal_hit();
+        req.defender.dynamax = true;
+        let result = req.calculate().unwrap();
+        assert_eq!(result.defender_hp, 400);
+        assert_eq!(result.max_percent, 9.25);
+    }
+
+    #[test]
+    fn test_fixed_and_variable_power() {
+        let mut req = request(attack(PokemonType::Fighting, 0, MoveCategory::Physical));
+        req.r#move.name = "Seismic Toss".to_string();
+        let toss = req.calculate().unwrap();
+        assert_eq!(toss.rolls, vec![50; NUM_ROLLS]);
+        req.defender.types = vec![PokemonType::Ghost];
+        assert_eq!(req.calculate().unwrap().max, 0);
+
+        req.defender.types = vec![PokemonType::Normal];
+        req.r#move.name = "Low Kick".to_string();
+        req.defender.weight = 2100;
+        assert_eq!(req.calculate().unwrap().power, 120);
+
+        // Gyro Ball uses battle Speed: a paralyzed target is half as fast
+        req.r#move = attack(PokemonType::Steel, 0, MoveCategory::Physical);
+        req.r#move.name = "Gyro Ball".to_string();
+        req.attacker.stats = Some(StatBlock::from_array([200, 100, 100, 100, 100, 50]));
+        assert_eq!(req.calculate().unwrap().power, 51);
+        req.defender.status = Status::Paralysis;
+        assert_eq!(req.calculate().unwrap().power, 26);
+    }
+
+    #[test]
+    fn test_multi_hit() {
+        let mut req = request(attack(PokemonType::Grass, 25, MoveCategory::Physical));
+        req.r#move.min_hits = 2;
+        req.r#move.max_hits = 5;
+        let seed = req.calculate().unwrap();
+        assert_eq!((seed.min_hits, seed.max_hits), (2, 5));
+        assert_eq!(seed.min, seed.rolls[0] * 2);
+        assert_eq!(seed.max, seed.rolls[15] * 5);
+
+        req.attacker.ability = "Skill Link".to_string();
+        let linked = req.calculate().unwrap();
+        assert_eq!((linked.min_hits, linked.min), (5, linked.rolls[0] * 5));
+    }
+
+    #[test]
+    fn test_request_ko_chance() {
+        let mut req = normal_hit();
+        req.defender.stats = Some(StatBlock::from_array([45, 100, 100, 100, 100, 100]));
+        let residual = ResidualSources::default();
+        // 31-37 per hit, 46-55 on a crit: only a crit OHKOs
+        let ko = req.ko_chance(&residual).unwrap();
+        assert_eq!(ko.text, "4.2% chance to OHKO");
+
+        req.defender.ability = "Shell Armor".to_string();
+        assert_eq!(req.ko_chance(&residual).unwrap().text, "guaranteed 2HKO");
+        req.r#move.critical = true;
+        assert_eq!(req.ko_chance(&residual).unwrap().text, "guaranteed OHKO");
+
+        // Defender's Leftovers are picked up automatically
+        req.r#move.critical = false;
+        req.defender.stats = Some(StatBlock::from_array([80, 100, 100, 100, 100, 100]));
+        req.defender.item = "Leftovers".to_string();
+        let ko = req.ko_chance(&residual).unwrap();
+        assert!(ko.text.ends_with("after Leftovers recovery"));
+    }
+}
@@ -0,0 +1,233 @@
+use pkmn_stats::apply_stat_stage;
+use pkmn_type_chart::normalize_name;
+use wasm_bindgen::prelude::*;
+
+use crate::formula::{damage_rolls, modify, DamageModifiers};
+
+/// Highest Toxic counter; the damage stops growing at 15/16 of max HP.
+pub const MAX_TOXIC_COUNTER: u32 = 15;
+
+/// Base power of a confusion self-hit.
+pub const CONFUSION_POWER: u32 = 40;
+
+/// End-of-turn damage from a major status condition.
+///
+/// - `burn`: 1/16 of max HP, 1/32 with Heatproof
+/// - `poison`: 1/8 of max HP
+/// - `toxic`: 1/16 of max HP (at least 1) times `toxic_counter` (the
+///   counter starts at 1 and is capped at `MAX_TOXIC_COUNTER`)
+///
+/// Magic Guard takes no damage; Poison Heal takes no poison damage (it
+/// heals instead). Other statuses deal no damage. `status` and `ability` are
+/// matched like every other name (see `normalize_name`). At least 1 HP is
+/// lost when damage applies.
+#[wasm_bindgen]
+pub fn status_damage(status: &str, max_hp: u32, toxic_counter: u32, ability: &str) -> u32 {
+    let ability = normalize_name(ability);
+    if ability == "magicguard" {
+        return 0;
+    }
+    let damage = match normalize_name(status).as_str() {
+        "burn" if ability == "heatproof" => max_hp / 32,
+        "burn" => max_hp / 16,
+        "poison" | "toxic" if ability == "poisonheal" => return 0,
+        "poison" => max_hp / 8,
+        "toxic" => (max_hp / 16).max(1) * toxic_counter.clamp(1, MAX_TOXIC_COUNTER),
+        _ => return 0,
+    };
+    damage.max(1)
+}
+
+/// All 16 rolls of a confusion self-hit: a typeless 40 power physical hit
+/// with the Pokemon's own Attack and Defense (stages applied), no critical
+/// hits, STAB or other modifiers. Magic Guard does not prevent it.
+#[wasm_bindgen]
+pub fn confusion_damage(
+    level: u32,
+    attack: u32,
+    defense: u32,
+    atk_stage: i8,
+    def_stage: i8,
+) -> Vec<u32> {
+    damage_rolls(
+        level,
+        CONFUSION_POWER,
+        apply_stat_stage(attack, atk_stage),
+        apply_stat_stage(defense, def_stage),
+        &DamageModifiers::default(),
+    )
+    .to_vec()
+}
+
+/// Share of the damage dealt that a recoil move costs its user, as
+/// `(numerator, denominator)`. Names are normalized.
+pub fn recoil_fraction(move_name: &str) -> Option<(u32, u32)> {
+    match normalize_name(move_name).as_str() {
+        "bravebird" | "doubleedge" | "flareblitz" | "volttackle" | "woodhammer" | "wavecrash" => {
+            Some((33, 100))
+        }
+        "headsmash" | "lightofruin" => Some((1, 2)),
+        "takedown" | "submission" | "wildcharge" | "headcharge" => Some((1, 4)),
+        _ => None,
+    }
+}
+
+/// Recoil taken by the attacker after dealing `damage_dealt` with a recoil
+/// move (Brave Bird and Double-Edge 33%, Head Smash 1/2, Take Down 1/4):
+/// rounded, at least 1. Rock Head and Magic Guard take none, as does a
+/// move without recoil or one that dealt no damage.
+#[wasm_bindgen]
+pub fn recoil_damage(move_name: &str, damage_dealt: u32, ability: &str) -> u32 {
+    let Some((num, den)) = recoil_fraction(move_name) else {
+        return 0;
+    };
+    if damage_dealt == 0 || matches!(normalize_name(ability).as_str(), "rockhead" | "magicguard") {
+        return 0;
+    }
+    ((damage_dealt as f64 * num as f64 / den as f64).round() as u32).max(1)
+}
+
+/// Share of the damage dealt that a draining move restores, as
+/// `(numerator, denominator)`. Names are normalized.
+pub fn drain_fraction(move_name: &str) -> Option<(u32, u32)> {
+    match normalize_name(move_name).as_str() {
+        "absorb" | "megadrain" | "gigadrain" | "drainpunch" | "leechlife" | "hornleech"
+        | "paraboliccharge" | "bitterblade" => Some((1, 2)),
+        "drainingkiss" | "oblivionwing" => Some((3, 4)),
+        _ => None,
+    }
+}
+
+/// HP change of the attacker after dealing `damage_dealt` with a draining
+/// move (Giga Drain, Drain Punch, ...): half the damage (3/4 for Draining
+/// Kiss and Oblivion Wing), rounded and at least 1, then 1.3x with Big
+/// Root.
+///
+/// Returns a positive amount healed, or a negative amount lost when the
+/// target has Liquid Ooze (none with Magic Guard). 0 for a move that does
+/// not drain or dealt no damage.
+#[wasm_bindgen]
+pub fn drain_hp(
+    move_name: &str,
+    damage_dealt: u32,
+    attacker_ability: &str,
+    attacker_item: &str,
+    defender_ability: &str,
+) -> i32 {
+    let Some((num, den)) = drain_fraction(move_name) else {
+        return 0;
+    };
+    if damage_dealt == 0 {
+        return 0;
+    }
+    let mut amount = ((damage_dealt as f64 * num as f64 / den as f64).round() as u64).max(1);
+    if normalize_name(attacker_item) == "bigroot" {
+        amount = modify(amount, 5324.0 / 4096.0);
+    }
+    if normalize_name(defender_ability) == "liquidooze" {
+        if normalize_name(attacker_ability) == "magicguard" {
+            0
+        } else {
+            -(amount as i32)
+        }
+    } else {
+        amount as i32
+    }
+}
+
+/// Damage taken by an attacker that makes contact: 1/8 of its max HP from
+/// Rough Skin or Iron Barbs plus 1/6 from Rocky Helmet, each at least 1.
+/// Magic Guard takes none. The caller decides whether the move makes
+/// contact.
+#[wasm_bindgen]
+pub fn contact_damage(
+    attacker_max_hp: u32,
+    attacker_ability: &str,
+    defender_ability: &str,
+    defender_item: &str,
+) -> u32 {
+    if normalize_name(attacker_ability) == "magicguard" {
+        return 0;
+    }
+    let mut damage = 0;
+    if matches!(
+        normalize_name(defender_ability).as_str(),
+        "roughskin" | "ironbarbs"
+    ) {
+        damage += (attacker_max_hp / 8).max(1);
+    }
+    if normalize_name(defender_item) == "rockyhelmet" {
+        damage += (attacker_max_hp / 6).max(1);
+    }
+    damage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_damage() {
+        assert_eq!(status_damage("burn", 160, 0, ""), 10);
+        assert_eq!(status_damage("Burn", 160, 0, "Heatproof"), 5);
+        assert_eq!(status_damage("poison", 160, 0, ""), 20);
+        assert_eq!(status_damage("toxic", 160, 1, ""), 10);
+        assert_eq!(status_damage("toxic", 160, 3, ""), 30);
+        assert_eq!(status_damage("toxic", 160, 20, ""), 150);
+        // 300 / 16 = 18 per counter step, not 3 * 300 / 16 = 56
+        assert_eq!(status_damage("toxic", 300, 3, ""), 54);
+        assert_eq!(status_damage("toxic", 10, 3, ""), 3);
+        assert_eq!(status_damage("toxic", 160, 3, "Poison Heal"), 0);
+        assert_eq!(status_damage("burn", 160, 0, "Magic Guard"), 0);
+        assert_eq!(status_damage("paralysis", 160, 0, ""), 0);
+        // Shedinja still loses its 1 HP
+        assert_eq!(status_damage("burn", 1, 0, ""), 1);
+    }
+
+    #[test]
+    fn test_confusion_damage() {
+        // floor(floor(22 * 40 * 100 / 100) / 50) + 2 = 19
+        let rolls = confusion_damage(50, 100, 100, 0, 0);
+        assert_eq!((rolls[0], rolls[15]), (16, 19));
+        // +2 Attack doubles the stat
+        assert_eq!(confusion_damage(50, 100, 100, 2, 0)[15], 37);
+    }
+
+    #[test]
+    fn test_recoil_damage() {
+        assert_eq!(recoil_damage("Brave Bird", 100, ""), 33);
+        assert_eq!(recoil_damage("Head Smash", 101, ""), 51);
+        assert_eq!(recoil_damage("Take Down", 2, ""), 1);
+        assert_eq!(recoil_damage("Head Smash", 100, "Rock Head"), 0);
+        assert_eq!(recoil_damage("Brave Bird", 100, "Magic Guard"), 0);
+        assert_eq!(recoil_damage("Earthquake", 100, ""), 0);
+        assert_eq!(recoil_damage("Brave Bird", 0, ""), 0);
+    }
+
+    #[test]
+    fn test_drain_hp() {
+        assert_eq!(drain_hp("Giga Drain", 100, "", "", ""), 50);
+        assert_eq!(drain_hp("Giga Drain", 1, "", "", ""), 1);
+        assert_eq!(drain_hp("Draining Kiss", 100, "", "", ""), 75);
+        // 50 * 5324 / 4096 = 64.99 -> 65
+        assert_eq!(drain_hp("Giga Drain", 100, "", "Big Root", ""), 65);
+        assert_eq!(drain_hp("Giga Drain", 100, "", "", "Liquid Ooze"), -50);
+        assert_eq!(
+            drain_hp("Giga Drain", 100, "Magic Guard", "", "Liquid Ooze"),
+            0
+        );
+        assert_eq!(drain_hp("Tackle", 100, "", "", ""), 0);
+    }
+
+    #[test]
+    fn test_contact_damage() {
+        assert_eq!(contact_damage(300, "", "Rough Skin", ""), 37);
+        assert_eq!(contact_damage(300, "", "", "Rocky Helmet"), 50);
+        assert_eq!(contact_damage(300, "", "Iron Barbs", "Rocky Helmet"), 87);
+        assert_eq!(
+            contact_damage(300, "Magic Guard", "Rough Skin", "Rocky Helmet"),
+            0
+        );
+        assert_eq!(contact_damage(300, "", "", ""), 0);
+    }
+}
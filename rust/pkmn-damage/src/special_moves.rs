@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+use pkmn_type_chart::normalize_name;
+
+/// Probability of each number of hits of a move, as `(hits, probability)`
+/// pairs sorted by hits.
+///
+/// - `min_hits`/`max_hits` of 0 or 1 mean a single hit
+/// - 2-5 hit moves (Bullet Seed, Rock Blast, ...): 35% / 35% / 15% / 15%,
+///   always 5 with Skill Link, 4 or 5 with Loaded Dice
+/// - Population Bomb (by name): up to 10 hits, each after the first
+///   checking its 90% accuracy; 10 with Skill Link, 4-10 with Loaded Dice
+/// - other ranges: every count equally likely
+pub fn hit_counts(
+    move_name: &str,
+    min_hits: u32,
+    max_hits: u32,
+    skill_link: bool,
+    loaded_dice: bool,
+) -> Vec<(u32, f64)> {
+    if normalize_name(move_name) == "populationbomb" {
+        if skill_link {
+            return vec![(10, 1.0)];
+        }
+        if loaded_dice {
+            return uniform_hits(4, 10);
+        }
+        return (1..=10)
+            .map(|hits| {
+                let all_hit = 0.9f64.powi(hits as i32 - 1);
+                (hits, if hits == 10 { all_hit } else { all_hit * 0.1 })
+            })
+            .collect();
+    }
+
+    let min_hits = min_hits.max(1);
+    let max_hits = max_hits.max(min_hits);
+    match (min_hits, max_hits) {
+        (min, max) if min == max => vec![(min, 1.0)],
+        (_, max) if skill_link => vec![(max, 1.0)],
+        (2, 5) if loaded_dice => uniform_hits(4, 5),
+        (2, 5) => vec![(2, 0.35), (3, 0.35), (4, 0.15), (5, 0.15)],
+        (min, max) => uniform_hits(min, max),
+    }
+}
+
+fn uniform_hits(min: u32, max: u32) -> Vec<(u32, f64)> {
+    let p = 1.0 / (max - min + 1) as f64;
+    (min..=max).map(|hits| (hits, p)).collect()
+}
+
+/// Distribution of the total damage of a multi-hit move.
+///
+/// `per_hit` is the `(damage, probability)` distribution of a single hit
+/// (see `hit_distribution`); every hit rolls independently. `hit_counts`
+/// comes from `hit_counts`. Returns `(damage, probability)` pairs sorted by damage.
+pub fn multi_hit_distribution(
+    per_hit: &[(u32, f64)],
+    hit_counts: &[(u32, f64)],
+) -> Vec<(u32, f64)> {
+    let max_hits = hit_counts.iter().map(|&(hits, _)| hits).max().unwrap_or(0);
+    let mut total: BTreeMap<u32, f64> = BTreeMap::new();
+    let mut after: BTreeMap<u32, f64> = BTreeMap::from([(0, 1.0)]);
+
+    for hits in 1..=max_hits {
+        let mut next = BTreeMap::new();
+        for (&sum, &p) in &after {
+            for &(damage, q) in per_hit {
+                *next.entry(sum + damage).or_insert(0.0) += p * q;
+            }
+        }
+        after = next;
+        let weight: f64 = hit_counts
+            .iter()
+            .filter(|&&(h, _)| h == hits)
+            .map(|&(_, p)| p)
+            .sum();
+        if weight > 0.0 {
+            for (&sum, &p) in &after {
+                *total.entry(sum).or_insert(0.0) += p * weight;
+            }
+        }
+    }
+    total.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hit_counts() {
+        assert_eq!(hit_counts("Tackle", 0, 0, false, false), vec![(1, 1.0)]);
+        assert_eq!(hit_counts("Double Kick", 2, 2, true, false), vec![(2, 1.0)]);
+        assert_eq!(hit_counts("Bullet Seed", 2, 5, false, false).len(), 4);
+        assert_eq!(hit_counts("Bullet Seed", 2, 5, true, false), vec![(5, 1.0)]);
+        assert_eq!(
+            hit_counts("Bullet Seed", 2, 5, false, true),
+            vec![(4, 0.5), (5, 0.5)]
+        );
+
+        let bomb = hit_counts("Population Bomb", 1, 10, false, false);
+        let total: f64 = bomb.iter().map(|&(_, p)| p).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+        assert!((bomb[9].1 - 0.9f64.powi(9)).abs() < 1e-12);
+        assert_eq!(
+            hit_counts("Population Bomb", 1, 10, true, false),
+            vec![(10, 1.0)]
+        );
+        assert_eq!(hit_counts("Population Bomb", 1, 10, false, true).len(), 7);
+    }
+
+    #[test]
+    fn test_multi_hit_distribution() {
+        // Two equally likely rolls of 10 or 20, hit twice
+        let per_hit = [(10, 0.5), (20, 0.5)];
+        let two = multi_hit_distribution(&per_hit, &[(2, 1.0)]);
+        assert_eq!(two, vec![(20, 0.25), (30, 0.5), (40, 0.25)]);
+
+        let mixed = multi_hit_distribution(&per_hit, &[(1, 0.5), (2, 0.5)]);
+        assert_eq!(mixed.first(), Some(&(10, 0.25)));
+        assert_eq!(mixed.last(), Some(&(40, 0.125)));
+        let total: f64 = mixed.iter().map(|&(_, p)| p).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+    }
+}
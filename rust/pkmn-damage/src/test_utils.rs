@@ -0,0 +1,38 @@
+use pkmn_stats::StatBlock;
+use pkmn_type_chart::PokemonType;
+
+use crate::request::{CalcField, CalcMove, CalcPokemon, DamageCalcRequest, MoveCategory};
+
+/// A level 50 Pokemon with the given final stats.
+pub(crate) fn mon_with_stats(types: &[PokemonType], stats: [u32; 6]) -> CalcPokemon {
+    CalcPokemon {
+        types: types.to_vec(),
+        stats: Some(StatBlock::from_array(stats)),
+        ..Default::default()
+    }
+}
+
+/// A level 50 Pokemon with 200 HP and 100 in every other stat.
+pub(crate) fn mon(types: &[PokemonType]) -> CalcPokemon {
+    mon_with_stats(types, [200, 100, 100, 100, 100, 100])
+}
+
+/// A move without special rules.
+pub(crate) fn attack(move_type: PokemonType, power: u32, category: MoveCategory) -> CalcMove {
+    CalcMove {
+        move_type,
+        power,
+        category,
+        ..Default::default()
+    }
+}
+
+/// A Fire attacker against a Normal defender, both from `mon`.
+pub(crate) fn request(mv: CalcMove) -> DamageCalcRequest {
+    DamageCalcRequest {
+        attacker: mon(&[PokemonType::Fire]),
+        defender: mon(&[PokemonType::Normal]),
+        r#move: mv,
+        field: CalcField::default(),
+    }
+}
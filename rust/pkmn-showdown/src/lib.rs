@@ -25,7 +25,7 @@ fn capitalize(s: &str) -> String {
 }
 
 fn to_display_name(api_name: &str) -> String {
-    api_name.split('-').map(|w| capitalize(w)).collect::<Vec<_>>().join(" ")
+    api_name.split('-').map(capitalize).collect::<Vec<_>>().join(" ")
 }
 
 /// Parse a Showdown paste block (single Pokemon) into a JSON string.
@@ -89,7 +89,7 @@ pub fn parse_showdown_block(block: &str) -> String {
         } else if line.ends_with("Nature") {
             nature = line.strip_suffix("Nature").unwrap_or("").trim().to_lowercase();
         } else if line.starts_with('-') || line.starts_with('\u{2013}') || line.starts_with('\u{2014}') {
-            let move_name = line.trim_start_matches(|c: char| c == '-' || c == '\u{2013}' || c == '\u{2014}' || c == ' ');
+            let move_name = line.trim_start_matches(['-', '\u{2013}', '\u{2014}', ' ']);
             if !move_name.is_empty() {
                 moves.push(to_api_name(move_name));
             }
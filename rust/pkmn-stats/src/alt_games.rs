@@ -0,0 +1,236 @@
+use wasm_bindgen::prelude::*;
+
+use crate::nature::{Nature, Stat};
+use crate::stat_block::StatBlock;
+
+/// Highest Awakening Value per stat in Let's Go, Pikachu! / Eevee!.
+pub const MAX_AV: u32 = 200;
+/// Highest effort level per stat in Legends: Arceus.
+pub const MAX_EFFORT_LEVEL: u32 = 10;
+pub const MAX_FRIENDSHIP: u32 = 255;
+
+/// Effort level multipliers for levels 0-10 in Legends: Arceus.
+const EFFORT_MULTIPLIERS: [f64; 11] = [0.0, 2.0, 3.0, 4.0, 7.0, 8.0, 9.0, 14.0, 15.0, 16.0, 25.0];
+
+// ---------------------------------------------------------------------------
+// Let's Go, Pikachu! / Eevee!
+// ---------------------------------------------------------------------------
+
+/// Let's Go friendship bonus in percent: floor(10 * friendship / 255), 0-10.
+/// Friendship above 255 is treated as 255.
+#[wasm_bindgen]
+pub fn lets_go_friendship_bonus(friendship: u32) -> u32 {
+    10 * friendship.min(MAX_FRIENDSHIP) / MAX_FRIENDSHIP
+}
+
+/// Calculate HP in Let's Go. There are no EVs; Awakening Values are added flat.
+///
+/// HP formula: floor((2*base + iv) * level / 100) + level + 10 + av
+#[wasm_bindgen]
+pub fn calculate_hp_lets_go(base: u32, iv: u32, av: u32, level: u32) -> u32 {
+    (2 * base + iv) * level / 100 + level + 10 + av.min(MAX_AV)
+}
+
+/// Calculate a single non-HP stat in Let's Go.
+///
+/// Stat formula: floor(floor((floor((2*base + iv) * level / 100) + 5) * nature) * friendship) + av,
+/// where the friendship multiplier is 1.00-1.10 (see `lets_go_friendship_bonus`).
+#[wasm_bindgen]
+pub fn calculate_stat_lets_go(
+    base: u32,
+    iv: u32,
+    av: u32,
+    nature_modifier: f64,
+    friendship: u32,
+    level: u32,
+) -> u32 {
+    let raw = (2 * base + iv) * level / 100 + 5;
+    let natured = (raw as f64 * nature_modifier) as u32;
+    natured * (100 + lets_go_friendship_bonus(friendship)) / 100 + av.min(MAX_AV)
+}
+
+/// Calculate all six stats in Let's Go.
+pub fn calculate_stats_lets_go(
+    base: &StatBlock,
+    ivs: &StatBlock,
+    avs: &StatBlock,
+    nature: Nature,
+    friendship: u32,
+    level: u32,
+) -> StatBlock {
+    let mut values = [0; 6];
+    for stat in Stat::ALL {
+        let (b, iv, av) = (base.get(stat), ivs.get(stat), avs.get(stat));
+        values[stat.index()] = match stat {
+            Stat::Hp => calculate_hp_lets_go(b, iv, av, level),
+            _ => calculate_stat_lets_go(b, iv, av, nature.modifier(stat), friendship, level),
+        };
+    }
+    StatBlock::from_array(values)
+}
+
+/// Calculate all six stats in Let's Go.
+///
+/// Parameters:
+/// - `base`, `ivs`, `avs`: `[hp, atk, def, spa, spd, spe]` arrays (AVs 0-200)
+/// - `nature`: nature name ("adamant") or in-game index ("3")
+/// - `friendship`: 0-255
+/// - `level`: the Pokemon's level
+///
+/// Returns `[hp, atk, def, spa, spd, spe]`, or an empty vector if an array
+/// does not have 6 values, an AV is above 200 or the nature is unknown.
+#[wasm_bindgen]
+pub fn calculate_all_stats_lets_go(
+    base: &[u32],
+    ivs: &[u32],
+    avs: &[u32],
+    nature: &str,
+    friendship: u32,
+    level: u32,
+) -> Vec<u32> {
+    let parsed = (
+        StatBlock::from_slice(base),
+        StatBlock::from_slice(ivs),
+        StatBlock::from_slice(avs),
+        nature.parse::<Nature>(),
+    );
+    let (Some(base), Some(ivs), Some(avs), Ok(nature)) = parsed else {
+        return Vec::new();
+    };
+    if avs.to_array().iter().any(|&av| av > MAX_AV) {
+        return Vec::new();
+    }
+    calculate_stats_lets_go(&base, &ivs, &avs, nature, friendship, level)
+        .to_array()
+        .to_vec()
+}
+
+// ---------------------------------------------------------------------------
+// Legends: Arceus
+// ---------------------------------------------------------------------------
+
+/// Stat bonus from effort levels in Legends: Arceus.
+///
+/// Bonus formula: floor((sqrt(base) * multiplier + level) / 2.5), where the
+/// multiplier for effort levels 0-10 is 0, 2, 3, 4, 7, 8, 9, 14, 15, 16, 25.
+/// Effort levels above 10 are treated as 10.
+#[wasm_bindgen]
+pub fn effort_level_bonus(base: u32, effort_level: u32, level: u32) -> u32 {
+    let multiplier = EFFORT_MULTIPLIERS[effort_level.min(MAX_EFFORT_LEVEL) as usize];
+    (((base as f64).sqrt() * multiplier + level as f64) / 2.5) as u32
+}
+
+/// Calculate HP in Legends: Arceus. IVs do not affect stats in this game.
+///
+/// HP formula: floor((level/100 + 1) * base + level) + effort_level_bonus
+#[wasm_bindgen]
+pub fn calculate_hp_legends(base: u32, effort_level: u32, level: u32) -> u32 {
+    (level + 100) * base / 100 + level + effort_level_bonus(base, effort_level, level)
+}
+
+/// Calculate a single non-HP stat in Legends: Arceus.
+///
+/// Stat formula: floor((floor((level/50 + 1) * base / 1.5) + effort_level_bonus) * nature)
+#[wasm_bindgen]
+pub fn calculate_stat_legends(base: u32, effort_level: u32, nature_modifier: f64, level: u32) -> u32 {
+    let raw = (level + 50) * base / 75 + effort_level_bonus(base, effort_level, level);
+    (raw as f64 * nature_modifier) as u32
+}
+
+/// Calculate all six stats in Legends: Arceus.
+pub fn calculate_stats_legends(
+    base: &StatBlock,
+    effort_levels: &StatBlock,
+    nature: Nature,
+    level: u32,
+) -> StatBlock {
+    let mut values = [0; 6];
+    for stat in Stat::ALL {
+        let (b, el) = (base.get(stat), effort_levels.get(stat));
+        values[stat.index()] = match stat {
+            Stat::Hp => calculate_hp_legends(b, el, level),
+            _ => calculate_stat_legends(b, el, nature.modifier(stat), level),
+        };
+    }
+    StatBlock::from_array(values)
+}
+
+/// Calculate all six stats in Legends: Arceus.
+///
+/// Parameters:
+/// - `base`, `effort_levels`: `[hp, atk, def, spa, spd, spe]` arrays (effort levels 0-10)
+/// - `nature`: nature name ("adamant") or in-game index ("3")
+/// - `level`: the Pokemon's level
+///
+/// Returns `[hp, atk, def, spa, spd, spe]`, or an empty vector if an array
+/// does not have 6 values, an effort level is above 10 or the nature is unknown.
+#[wasm_bindgen]
+pub fn calculate_all_stats_legends(
+    base: &[u32],
+    effort_levels: &[u32],
+    nature: &str,
+    level: u32,
+) -> Vec<u32> {
+    let parsed = (
+        StatBlock::from_slice(base),
+        StatBlock::from_slice(effort_levels),
+        nature.parse::<Nature>(),
+    );
+    let (Some(base), Some(effort_levels), Ok(nature)) = parsed else {
+        return Vec::new();
+    };
+    if effort_levels.to_array().iter().any(|&el| el > MAX_EFFORT_LEVEL) {
+        return Vec::new();
+    }
+    calculate_stats_legends(&base, &effort_levels, nature, level)
+        .to_array()
+        .to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lets_go_friendship() {
+        assert_eq!(lets_go_friendship_bonus(0), 0);
+        assert_eq!(lets_go_friendship_bonus(128), 5);
+        assert_eq!(lets_go_friendship_bonus(255), 10);
+        assert_eq!(lets_go_friendship_bonus(999), 10);
+        // Base 100, 31 IV, level 50: 120 before friendship
+        assert_eq!(calculate_stat_lets_go(100, 31, 0, 1.0, 0, 50), 120);
+        assert_eq!(calculate_stat_lets_go(100, 31, 0, 1.0, 255, 50), 132);
+    }
+
+    #[test]
+    fn test_lets_go_awakening_values() {
+        assert_eq!(calculate_stat_lets_go(100, 31, 200, 1.0, 255, 50), 332);
+        assert_eq!(calculate_hp_lets_go(100, 31, 200, 50), 115 + 60 + 200);
+        // AVs are capped at 200
+        assert_eq!(calculate_hp_lets_go(100, 31, 250, 50), 375);
+
+        let stats = calculate_all_stats_lets_go(&[100; 6], &[31; 6], &[200; 6], "adamant", 255, 50);
+        // Nature applies before friendship: floor(floor(120 * 1.1) * 1.1) + 200
+        assert_eq!(stats[1], 145 + 200);
+        assert!(calculate_all_stats_lets_go(&[100; 6], &[31; 6], &[201; 6], "adamant", 255, 50).is_empty());
+    }
+
+    #[test]
+    fn test_legends_effort_levels() {
+        // Level 100, EL 0: floor(100 / 2.5) = 40
+        assert_eq!(effort_level_bonus(130, 0, 100), 40);
+        // sqrt(108) * 25 = 259.8; (259.8 + 100) / 2.5 = 143.9
+        assert_eq!(effort_level_bonus(108, 10, 100), 143);
+        assert_eq!(effort_level_bonus(108, 11, 100), 143);
+        assert_eq!(calculate_hp_legends(108, 10, 100), 316 + 143);
+        assert_eq!(calculate_stat_legends(130, 0, 1.0, 100), 260 + 40);
+        assert_eq!(calculate_stat_legends(100, 0, 1.0, 50), 133 + 20);
+    }
+
+    #[test]
+    fn test_legends_all_stats() {
+        let stats = calculate_all_stats_legends(&[100; 6], &[0; 6], "hardy", 50);
+        assert_eq!(stats, vec![220, 153, 153, 153, 153, 153]);
+        assert!(calculate_all_stats_legends(&[100; 6], &[11; 6], "hardy", 50).is_empty());
+    }
+}
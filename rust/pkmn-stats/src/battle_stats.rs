@@ -0,0 +1,249 @@
+use pkmn_type_chart::normalize_name;
+use wasm_bindgen::prelude::*;
+
+/// Weather ids shared with pkmn-damage: 0 = none, 1 = sun, 2 = rain,
+/// 3 = sandstorm, 4 = hail, 5 = snow, 6 = harsh sunlight, 7 = heavy rain.
+pub const WEATHER_SUN: u8 = 1;
+pub const WEATHER_RAIN: u8 = 2;
+pub const WEATHER_SAND: u8 = 3;
+pub const WEATHER_HAIL: u8 = 4;
+pub const WEATHER_SNOW: u8 = 5;
+pub const WEATHER_HARSH_SUN: u8 = 6;
+pub const WEATHER_HEAVY_RAIN: u8 = 7;
+
+/// The regular weather a weather id counts as for abilities and moves:
+/// harsh sunlight is sun, heavy rain is rain and snow is hail.
+pub fn base_weather(weather: u8) -> u8 {
+    match weather {
+        WEATHER_HARSH_SUN => WEATHER_SUN,
+        WEATHER_HEAVY_RAIN => WEATHER_RAIN,
+        WEATHER_SNOW => WEATHER_HAIL,
+        other => other,
+    }
+}
+
+/// Apply a stat stage (-6 to +6, clamped) to a stat.
+///
+/// Positive stages multiply by (2 + stage) / 2, negative stages by 2 / (2 - stage).
+pub fn apply_stat_stage(stat: u32, stage: i8) -> u32 {
+    let stage = stage.clamp(-6, 6);
+    if stage >= 0 {
+        stat * (2 + stage as u32) / 2
+    } else {
+        stat * 2 / (2 + stage.unsigned_abs() as u32)
+    }
+}
+
+/// Stage that counts for a critical hit.
+///
+/// - `favorable = true` (attacker's offensive stat): negative stages are ignored
+/// - `favorable = false` (defender's defensive stat): positive stages are ignored
+pub fn critical_hit_stage(stage: i8, favorable: bool) -> i8 {
+    if favorable {
+        stage.max(0)
+    } else {
+        stage.min(0)
+    }
+}
+
+/// Battle state that modifies a Pokemon's stats beyond stat stages.
+///
+/// Ability and item names are matched case-insensitively, ignoring spaces
+/// and hyphens. Empty strings mean "none".
+#[wasm_bindgen]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BattleStatModifiers {
+    ability: String,
+    item: String,
+    weather: u8,
+    paralyzed: bool,
+    statused: bool,
+    tailwind: bool,
+    item_lost: bool,
+}
+
+#[wasm_bindgen]
+impl BattleStatModifiers {
+    /// Parameters:
+    /// - `ability`, `item`: the Pokemon's ability and held item
+    /// - `weather`: 0 = none, 1 = sun, 2 = rain, 3 = sandstorm, 4 = hail,
+    ///   5 = snow, 6 = harsh sunlight, 7 = heavy rain
+    /// - `paralyzed`: the Pokemon is paralyzed
+    /// - `statused`: the Pokemon has any major status (for Quick Feet)
+    /// - `tailwind`: Tailwind is active on the Pokemon's side
+    /// - `item_lost`: the Pokemon's held item was consumed or removed (for Unburden)
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        ability: &str,
+        item: &str,
+        weather: u8,
+        paralyzed: bool,
+        statused: bool,
+        tailwind: bool,
+        item_lost: bool,
+    ) -> BattleStatModifiers {
+        BattleStatModifiers {
+            ability: normalize_name(ability),
+            item: normalize_name(item),
+            weather,
+            paralyzed,
+            statused: statused || paralyzed,
+            tailwind,
+            item_lost,
+        }
+    }
+
+    /// Speed in battle after stages and modifiers.
+    ///
+    /// Order: stage, then Choice Scarf (1.5x), weather abilities (Swift Swim,
+    /// Chlorophyll, Sand Rush, Slush Rush: 2x), Unburden (2x), Quick Feet
+    /// (1.5x) and Tailwind (2x) chained into one 4096-based modifier and
+    /// applied once, then paralysis (0.5x, skipped with Quick Feet), floored
+    /// separately.
+    pub fn speed(&self, speed: u32, stage: i8) -> u32 {
+        let speed = apply_stat_stage(speed, stage);
+        let quick_feet = self.ability == "quickfeet" && self.statused;
+        let weather_ability = matches!(
+            (self.ability.as_str(), base_weather(self.weather)),
+            ("swiftswim", WEATHER_RAIN)
+                | ("chlorophyll", WEATHER_SUN)
+                | ("sandrush", WEATHER_SAND)
+                | ("slushrush", WEATHER_HAIL)
+        );
+
+        let mut modifiers = Vec::new();
+        if self.held_item() == "choicescarf" {
+            modifiers.push(6144);
+        }
+        if weather_ability {
+            modifiers.push(8192);
+        }
+        if self.ability == "unburden" && self.item_lost {
+            modifiers.push(8192);
+        }
+        if quick_feet {
+            modifiers.push(6144);
+        }
+        if self.tailwind {
+            modifiers.push(8192);
+        }
+        let mut speed = apply_modifier(speed, chain_modifiers(&modifiers));
+        if self.paralyzed && !quick_feet {
+            speed /= 2;
+        }
+        speed
+    }
+
+    /// Attack (`physical = true`) or Sp. Atk in battle after stages and
+    /// Choice Band / Choice Specs (1.5x).
+    pub fn attack(&self, stat: u32, stage: i8, physical: bool) -> u32 {
+        let stat = apply_stat_stage(stat, stage);
+        match (self.held_item(), physical) {
+            ("choiceband", true) | ("choicespecs", false) => stat * 3 / 2,
+            _ => stat,
+        }
+    }
+}
+
+impl BattleStatModifiers {
+    fn held_item(&self) -> &str {
+        if self.item_lost {
+            ""
+        } else {
+            &self.item
+        }
+    }
+}
+
+/// Chain 4096-based modifiers into one, rounding to 1/4096 after each step.
+fn chain_modifiers(modifiers: &[u64]) -> u64 {
+    modifiers
+        .iter()
+        .fold(4096, |acc, &m| (acc * m + 2048) >> 12)
+}
+
+/// Apply a 4096-based modifier to a stat, rounding halves down.
+fn apply_modifier(stat: u32, modifier: u64) -> u32 {
+    ((stat as u64 * modifier + 2047) / 4096) as u32
+}
+
+/// Speed in battle after stages and modifiers. See `BattleStatModifiers::new`
+/// for the parameters and `BattleStatModifiers::speed` for the order of operations.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn get_battle_speed(
+    speed: u32,
+    stage: i8,
+    ability: &str,
+    item: &str,
+    weather: u8,
+    paralyzed: bool,
+    statused: bool,
+    tailwind: bool,
+    item_lost: bool,
+) -> u32 {
+    BattleStatModifiers::new(ability, item, weather, paralyzed, statused, tailwind, item_lost)
+        .speed(speed, stage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mods(ability: &str, item: &str, weather: u8) -> BattleStatModifiers {
+        BattleStatModifiers::new(ability, item, weather, false, false, false, false)
+    }
+
+    #[test]
+    fn test_stat_stages() {
+        assert_eq!(apply_stat_stage(100, 0), 100);
+        assert_eq!(apply_stat_stage(100, 1), 150);
+        assert_eq!(apply_stat_stage(101, 1), 151);
+        assert_eq!(apply_stat_stage(100, 6), 400);
+        assert_eq!(apply_stat_stage(100, -1), 66);
+        assert_eq!(apply_stat_stage(100, -6), 25);
+        assert_eq!(apply_stat_stage(100, 12), 400);
+        assert_eq!(critical_hit_stage(-2, true), 0);
+        assert_eq!(critical_hit_stage(2, false), 0);
+        assert_eq!(critical_hit_stage(-2, false), -2);
+    }
+
+    #[test]
+    fn test_speed_items_and_abilities() {
+        assert_eq!(mods("", "Choice Scarf", 0).speed(100, 0), 150);
+        assert_eq!(mods("Swift Swim", "", WEATHER_RAIN).speed(100, 0), 200);
+        assert_eq!(mods("Swift Swim", "", WEATHER_SUN).speed(100, 0), 100);
+        assert_eq!(mods("chlorophyll", "", WEATHER_SUN).speed(100, 1), 300);
+        // Scarf and weather chain to 3x before rounding: 101 -> 303, not 151 -> 302
+        assert_eq!(mods("swift-swim", "choice-scarf", WEATHER_RAIN).speed(101, 0), 303);
+        assert_eq!(mods("Chlorophyll", "", WEATHER_HARSH_SUN).speed(100, 0), 200);
+        assert_eq!(mods("Slush Rush", "", WEATHER_SNOW).speed(100, 0), 200);
+        assert_eq!(base_weather(WEATHER_HEAVY_RAIN), WEATHER_RAIN);
+        assert_eq!(base_weather(WEATHER_SAND), WEATHER_SAND);
+    }
+
+    #[test]
+    fn test_speed_status_and_field() {
+        let paralyzed = BattleStatModifiers::new("", "", 0, true, false, false, false);
+        assert_eq!(paralyzed.speed(101, 0), 50);
+        // Paralysis is floored on its own after the chained modifiers: 101 * 1.5 = 151.5 -> 151 -> 75
+        let scarf_paralyzed = BattleStatModifiers::new("", "Choice Scarf", 0, true, false, false, false);
+        assert_eq!(scarf_paralyzed.speed(101, 0), 75);
+        let quick_feet = BattleStatModifiers::new("Quick Feet", "", 0, true, false, false, false);
+        assert_eq!(quick_feet.speed(100, 0), 150);
+        let tailwind = BattleStatModifiers::new("", "", 0, true, false, true, false);
+        assert_eq!(tailwind.speed(100, 0), 100);
+        assert_eq!(get_battle_speed(100, 0, "Unburden", "", 0, false, false, false, true), 200);
+        assert_eq!(get_battle_speed(100, 0, "Unburden", "", 0, false, false, false, false), 100);
+        // A consumed Scarf no longer applies
+        assert_eq!(get_battle_speed(100, 0, "", "Choice Scarf", 0, false, false, false, true), 100);
+    }
+
+    #[test]
+    fn test_choice_attack_items() {
+        assert_eq!(mods("", "Choice Band", 0).attack(100, 0, true), 150);
+        assert_eq!(mods("", "Choice Band", 0).attack(100, 0, false), 100);
+        assert_eq!(mods("", "Choice Specs", 0).attack(100, 1, false), 225);
+        assert_eq!(mods("", "", 0).attack(100, -1, true), 66);
+    }
+}
@@ -0,0 +1,284 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::battle_stats::apply_stat_stage;
+use crate::nature::{Nature, Stat};
+use crate::stat_block::{calculate_stats, StatBlock};
+use crate::{calculate_hp, calculate_stat};
+
+pub const MAX_TOTAL_EVS: u32 = 510;
+pub const MAX_STAT_EVS: u32 = 252;
+/// EVs only count in steps of 4 (`floor(ev / 4)` in the stat formula).
+const EV_STEP: usize = 4;
+
+/// A requirement for `optimize_evs`. Goals are satisfied in order, each with
+/// the fewest extra EVs on top of what earlier goals already invested.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum EvGoal {
+    /// Speed, after applying `stage` (-6 to +6), strictly above `target_speed`.
+    /// `target_speed` is the opponent's final Speed, e.g. the result of
+    /// `calculate_stat` for a 135 base Jolly Pokemon.
+    Outspeed {
+        target_speed: u32,
+        #[serde(default)]
+        stage: i8,
+    },
+    /// Survive the highest damage roll of an attack with at least 1 HP.
+    Survive {
+        /// Attacker's level, Attack or Sp. Atk (stages already applied) and move power.
+        attacker_level: u32,
+        attack: u32,
+        power: u32,
+        /// True to invest in Defense, false for Sp. Def.
+        physical: bool,
+        /// Product of all other multipliers (STAB, type effectiveness, items, ...).
+        #[serde(default = "one")]
+        modifier: f64,
+    },
+    /// Spend the remaining EVs on HP and Defense (or Sp. Def) to maximise
+    /// HP * Def. Should be the last goal.
+    MaximizeBulk { physical: bool },
+}
+
+fn one() -> f64 {
+    1.0
+}
+
+/// Result of a successful optimisation.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OptimizedSpread {
+    pub evs: StatBlock,
+    pub stats: StatBlock,
+}
+
+/// Highest damage roll of an attack, using the standard base damage formula
+/// followed by a single combined `modifier`.
+fn max_damage(attacker_level: u32, power: u32, attack: u32, defense: u32, modifier: f64) -> u32 {
+    let level_factor = 2 * attacker_level / 5 + 2;
+    let base = level_factor * power * attack / defense.max(1) / 50 + 2;
+    (base as f64 * modifier) as u32
+}
+
+struct Solver<'a> {
+    base: &'a StatBlock,
+    ivs: &'a StatBlock,
+    nature: Nature,
+    level: u32,
+    evs: [u32; 6],
+}
+
+impl Solver<'_> {
+    fn stat(&self, stat: Stat, ev: u32) -> u32 {
+        let (base, iv) = (self.base.get(stat), self.ivs.get(stat));
+        match stat {
+            Stat::Hp => calculate_hp(base, iv, ev, self.level),
+            _ => calculate_stat(base, iv, ev, self.nature.modifier(stat), self.level),
+        }
+    }
+
+    fn remaining(&self) -> u32 {
+        MAX_TOTAL_EVS - self.evs.iter().sum::<u32>()
+    }
+
+    /// EV values for `stat` from the current investment up to the cap, in steps of 4.
+    fn candidates(&self, stat: Stat) -> impl Iterator<Item = u32> {
+        let current = self.evs[stat.index()];
+        let limit = MAX_STAT_EVS.min(current + self.remaining());
+        (current..=limit).step_by(EV_STEP)
+    }
+
+    fn outspeed(&mut self, target_speed: u32, stage: i8) -> bool {
+        let found = self
+            .candidates(Stat::Spe)
+            .find(|&ev| apply_stat_stage(self.stat(Stat::Spe, ev), stage) > target_speed);
+        found.map(|ev| self.evs[Stat::Spe.index()] = ev).is_some()
+    }
+
+    /// Cheapest HP/defense pair that survives the hit. Ties prefer the pair
+    /// with the larger HP * Def product.
+    fn survive(&mut self, level: u32, attack: u32, power: u32, def_stat: Stat, modifier: f64) -> bool {
+        let spent = self.evs[Stat::Hp.index()] + self.evs[def_stat.index()];
+        let mut best: Option<(u32, u64, u32, u32)> = None;
+        for hp_ev in self.candidates(Stat::Hp) {
+            let hp = self.stat(Stat::Hp, hp_ev);
+            let def_ev = self.candidates(def_stat).find(|&ev| {
+                hp_ev + ev - spent <= self.remaining()
+                    && max_damage(level, power, attack, self.stat(def_stat, ev), modifier) < hp
+            });
+            if let Some(def_ev) = def_ev {
+                let cost = hp_ev + def_ev - spent;
+                let product = hp as u64 * self.stat(def_stat, def_ev) as u64;
+                if best.is_none_or(|(c, p, _, _)| cost < c || (cost == c && product > p)) {
+                    best = Some((cost, product, hp_ev, def_ev));
+                }
+            }
+        }
+        best.map(|(_, _, hp_ev, def_ev)| {
+            self.evs[Stat::Hp.index()] = hp_ev;
+            self.evs[def_stat.index()] = def_ev;
+        })
+        .is_some()
+    }
+
+    fn maximize_bulk(&mut self, def_stat: Stat) {
+        let spent = self.evs[Stat::Hp.index()] + self.evs[def_stat.index()];
+        let mut best = (0u64, self.evs[Stat::Hp.index()], self.evs[def_stat.index()]);
+        for hp_ev in self.candidates(Stat::Hp) {
+            for def_ev in self.candidates(def_stat) {
+                if hp_ev + def_ev - spent > self.remaining() {
+                    break;
+                }
+                let product = self.stat(Stat::Hp, hp_ev) as u64 * self.stat(def_stat, def_ev) as u64;
+                if product > best.0 {
+                    best = (product, hp_ev, def_ev);
+                }
+            }
+        }
+        self.evs[Stat::Hp.index()] = best.1;
+        self.evs[def_stat.index()] = best.2;
+    }
+}
+
+/// Find the smallest legal EV spread (at most 252 per stat and 510 in total,
+/// in multiples of 4) that meets every goal, in order.
+///
+/// Returns `None` if a goal cannot be met with the EVs left.
+pub fn optimize_evs(
+    base: &StatBlock,
+    ivs: &StatBlock,
+    nature: Nature,
+    level: u32,
+    goals: &[EvGoal],
+) -> Option<OptimizedSpread> {
+    let mut solver = Solver { base, ivs, nature, level, evs: [0; 6] };
+    for goal in goals {
+        let met = match *goal {
+            EvGoal::Outspeed { target_speed, stage } => solver.outspeed(target_speed, stage),
+            EvGoal::Survive { attacker_level, attack, power, physical, modifier } => {
+                let def_stat = if physical { Stat::Def } else { Stat::SpD };
+                solver.survive(attacker_level, attack, power, def_stat, modifier)
+            }
+            EvGoal::MaximizeBulk { physical } => {
+                solver.maximize_bulk(if physical { Stat::Def } else { Stat::SpD });
+                true
+            }
+        };
+        if !met {
+            return None;
+        }
+    }
+
+    let evs = StatBlock::from_array(solver.evs);
+    let stats = calculate_stats(base, ivs, &evs, nature, level);
+    Some(OptimizedSpread { evs, stats })
+}
+
+/// Find the smallest legal EV spread meeting a list of goals.
+///
+/// Parameters:
+/// - `base`, `ivs`: `[hp, atk, def, spa, spd, spe]` arrays
+/// - `nature`: nature name ("adamant") or in-game index ("3")
+/// - `level`: the Pokemon's level
+/// - `goals`: array of goal objects, satisfied in order:
+///   - `{ kind: "outspeed", targetSpeed, stage? }`
+///   - `{ kind: "survive", attackerLevel, attack, power, physical, modifier? }`
+///   - `{ kind: "maximizeBulk", physical }`
+///
+/// Returns `{ evs, stats }` with `{ hp, attack, defense, spAtk, spDef, speed }`
+/// objects, or `null` if the input is invalid or the goals cannot all be met.
+#[wasm_bindgen]
+pub fn optimize_ev_spread(base: &[u32], ivs: &[u32], nature: &str, level: u32, goals: JsValue) -> JsValue {
+    let parsed = (
+        StatBlock::from_slice(base),
+        StatBlock::from_slice(ivs),
+        nature.parse::<Nature>(),
+        serde_wasm_bindgen::from_value::<Vec<EvGoal>>(goals),
+    );
+    let (Some(base), Some(ivs), Ok(nature), Ok(goals)) = parsed else {
+        return JsValue::NULL;
+    };
+    match optimize_evs(&base, &ivs, nature, level, &goals) {
+        Some(result) => serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL),
+        None => JsValue::NULL,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Garchomp: 108 / 130 / 95 / 80 / 85 / 102
+    const GARCHOMP: [u32; 6] = [108, 130, 95, 80, 85, 102];
+
+    fn garchomp(nature: &str, goals: &[EvGoal]) -> Option<OptimizedSpread> {
+        let base = StatBlock::from_array(GARCHOMP);
+        optimize_evs(&base, &StatBlock::uniform(31), nature.parse().unwrap(), 50, goals)
+    }
+
+    #[test]
+    fn test_outspeed_minimal_evs() {
+        let result = garchomp("jolly", &[EvGoal::Outspeed { target_speed: 150, stage: 0 }]).unwrap();
+        assert_eq!(result.evs.speed, 124);
+        assert!(result.stats.speed > 150);
+        assert!(calculate_stat(102, 31, 120, 1.1, 50) <= 150);
+        assert_eq!(result.evs.speed % 4, 0);
+        assert_eq!(result.evs.hp + result.evs.defense, 0);
+    }
+
+    #[test]
+    fn test_outspeed_at_plus_one() {
+        // Max Speed Jolly base 135 at level 50: 205. At +1, Garchomp needs > 102.
+        let target = calculate_stat(135, 31, 252, 1.1, 50);
+        assert_eq!(target, 205);
+        let result = garchomp("adamant", &[EvGoal::Outspeed { target_speed: target, stage: 1 }]);
+        let result = result.unwrap();
+        assert!(apply_stat_stage(result.stats.speed, 1) > target);
+        assert!(apply_stat_stage(calculate_stat(102, 31, result.evs.speed - 4, 1.0, 50), 1) <= target);
+        assert!(garchomp("modest", &[EvGoal::Outspeed { target_speed: target, stage: 0 }]).is_none());
+    }
+
+    #[test]
+    fn test_survive_hit() {
+        let hit = EvGoal::Survive {
+            attacker_level: 50,
+            attack: 300,
+            power: 120,
+            physical: true,
+            modifier: 1.5,
+        };
+        let result = garchomp("impish", &[hit]).unwrap();
+        let dmg = max_damage(50, 120, 300, result.stats.defense, 1.5);
+        assert!(dmg < result.stats.hp);
+        assert!(result.evs.to_array().iter().sum::<u32>() < MAX_TOTAL_EVS);
+        // Uninvested Garchomp does not survive
+        let bare = garchomp("impish", &[]).unwrap();
+        assert!(max_damage(50, 120, 300, bare.stats.defense, 1.5) >= bare.stats.hp);
+    }
+
+    #[test]
+    fn test_goals_stack_and_fill_bulk() {
+        let goals = [
+            EvGoal::Outspeed { target_speed: 150, stage: 0 },
+            EvGoal::MaximizeBulk { physical: true },
+        ];
+        let result = garchomp("jolly", &goals).unwrap();
+        let evs = result.evs.to_array();
+        assert!(evs.iter().all(|&ev| ev <= MAX_STAT_EVS && ev % 4 == 0));
+        assert!(evs.iter().sum::<u32>() <= MAX_TOTAL_EVS);
+        assert!(evs.iter().sum::<u32>() > MAX_TOTAL_EVS - 8);
+        assert!(result.stats.speed > 150);
+    }
+
+    #[test]
+    fn test_impossible_survive() {
+        let hit = EvGoal::Survive {
+            attacker_level: 100,
+            attack: 600,
+            power: 250,
+            physical: false,
+            modifier: 4.0,
+        };
+        assert!(garchomp("calm", &[hit]).is_none());
+    }
+}
@@ -0,0 +1,207 @@
+use wasm_bindgen::prelude::*;
+
+use crate::stat_block::StatBlock;
+
+/// Highest Determinant Value (Gen 1/2 IV).
+pub const MAX_DV: u32 = 15;
+/// Highest Stat Experience per stat.
+pub const MAX_STAT_EXP: u32 = 65535;
+
+/// Gen 1/2 DVs. There is no HP DV of its own: it is built from the lowest
+/// bit of the other four (see `hp`). Special is a single DV shared by
+/// Sp. Atk and Sp. Def in Gen 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dvs {
+    pub attack: u32,
+    pub defense: u32,
+    pub speed: u32,
+    pub special: u32,
+}
+
+impl Dvs {
+    pub const MAX: Dvs = Dvs {
+        attack: MAX_DV,
+        defense: MAX_DV,
+        speed: MAX_DV,
+        special: MAX_DV,
+    };
+
+    /// HP DV: the lowest bits of Attack, Defense, Speed and Special, in that order.
+    pub fn hp(&self) -> u32 {
+        hp_dv_from_dvs(self.attack, self.defense, self.speed, self.special)
+    }
+}
+
+/// Gen 1/2 Stat Experience, one value (0-65535) per stat, with a single
+/// Special value shared by Sp. Atk and Sp. Def.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StatExp {
+    pub hp: u32,
+    pub attack: u32,
+    pub defense: u32,
+    pub speed: u32,
+    pub special: u32,
+}
+
+impl StatExp {
+    pub const MAX: StatExp = StatExp {
+        hp: MAX_STAT_EXP,
+        attack: MAX_STAT_EXP,
+        defense: MAX_STAT_EXP,
+        speed: MAX_STAT_EXP,
+        special: MAX_STAT_EXP,
+    };
+}
+
+/// Derive the HP DV from the other four DVs.
+///
+/// HP DV = (atk & 1) << 3 | (def & 1) << 2 | (spe & 1) << 1 | (spc & 1)
+#[wasm_bindgen]
+pub fn hp_dv_from_dvs(atk_dv: u32, def_dv: u32, spe_dv: u32, spc_dv: u32) -> u32 {
+    (atk_dv & 1) << 3 | (def_dv & 1) << 2 | (spe_dv & 1) << 1 | (spc_dv & 1)
+}
+
+/// Stat Experience contribution to a stat: floor(min(255, ceil(sqrt(stat_exp))) / 4).
+/// Values above 65535 are treated as 65535. Maxes out at 63.
+#[wasm_bindgen]
+pub fn stat_exp_bonus(stat_exp: u32) -> u32 {
+    let exp = stat_exp.min(MAX_STAT_EXP);
+    let mut root = exp.isqrt();
+    if root * root < exp {
+        root += 1;
+    }
+    root.min(255) / 4
+}
+
+/// Calculate Gen 1/2 HP.
+///
+/// HP formula: floor(((base + dv) * 2 + stat_exp_bonus) * level / 100) + level + 10
+#[wasm_bindgen]
+pub fn calculate_hp_gen12(base: u32, dv: u32, stat_exp: u32, level: u32) -> u32 {
+    ((base + dv) * 2 + stat_exp_bonus(stat_exp)) * level / 100 + level + 10
+}
+
+/// Calculate a single Gen 1/2 non-HP stat. There are no natures.
+///
+/// Stat formula: floor(((base + dv) * 2 + stat_exp_bonus) * level / 100) + 5
+#[wasm_bindgen]
+pub fn calculate_stat_gen12(base: u32, dv: u32, stat_exp: u32, level: u32) -> u32 {
+    ((base + dv) * 2 + stat_exp_bonus(stat_exp)) * level / 100 + 5
+}
+
+/// Calculate all stats in Gen 1 or 2.
+///
+/// In Gen 1 there is a single Special stat: it is computed from `base.sp_atk`
+/// and returned as both Sp. Atk and Sp. Def (`base.sp_def` is ignored).
+/// In Gen 2 the two bases differ but share the Special DV and Stat Experience.
+pub fn calculate_stats_gen12(
+    generation: u8,
+    base: &StatBlock,
+    dvs: &Dvs,
+    stat_exp: &StatExp,
+    level: u32,
+) -> StatBlock {
+    let special_def_base = if generation == 1 { base.sp_atk } else { base.sp_def };
+    let stat = |b, dv, exp| calculate_stat_gen12(b, dv, exp, level);
+    StatBlock {
+        hp: calculate_hp_gen12(base.hp, dvs.hp(), stat_exp.hp, level),
+        attack: stat(base.attack, dvs.attack, stat_exp.attack),
+        defense: stat(base.defense, dvs.defense, stat_exp.defense),
+        sp_atk: stat(base.sp_atk, dvs.special, stat_exp.special),
+        sp_def: stat(special_def_base, dvs.special, stat_exp.special),
+        speed: stat(base.speed, dvs.speed, stat_exp.speed),
+    }
+}
+
+/// Calculate all stats with the Gen 1/2 formulas.
+///
+/// Parameters:
+/// - `generation`: 1 or 2
+/// - `base`: `[hp, atk, def, spa, spd, spe]`; for Gen 1 put the Special base in `spa`
+/// - `dvs`: `[atk, def, spe, special]`, each 0-15 (the HP DV is derived)
+/// - `stat_exp`: `[hp, atk, def, spe, special]`, each 0-65535
+/// - `level`: the Pokemon's level
+///
+/// Returns `[hp, atk, def, spa, spd, spe]` (in Gen 1, spa = spd = Special),
+/// or an empty vector if the generation is not 1 or 2, an array has the
+/// wrong length, or a DV is above 15.
+#[wasm_bindgen]
+pub fn calculate_all_stats_gen12(
+    generation: u8,
+    base: &[u32],
+    dvs: &[u32],
+    stat_exp: &[u32],
+    level: u32,
+) -> Vec<u32> {
+    let (Some(base), &[attack, defense, speed, special], &[hp_exp, atk_exp, def_exp, spe_exp, spc_exp]) =
+        (StatBlock::from_slice(base), dvs, stat_exp)
+    else {
+        return Vec::new();
+    };
+    if !(1..=2).contains(&generation) || [attack, defense, speed, special].iter().any(|&dv| dv > MAX_DV) {
+        return Vec::new();
+    }
+
+    let dvs = Dvs { attack, defense, speed, special };
+    let stat_exp = StatExp {
+        hp: hp_exp,
+        attack: atk_exp,
+        defense: def_exp,
+        speed: spe_exp,
+        special: spc_exp,
+    };
+    calculate_stats_gen12(generation, &base, &dvs, &stat_exp, level)
+        .to_array()
+        .to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hp_dv() {
+        assert_eq!(hp_dv_from_dvs(15, 15, 15, 15), 15);
+        assert_eq!(hp_dv_from_dvs(15, 14, 13, 12), 10);
+        assert_eq!(hp_dv_from_dvs(0, 0, 0, 1), 1);
+        assert_eq!(Dvs::MAX.hp(), 15);
+    }
+
+    #[test]
+    fn test_stat_exp_rounding() {
+        assert_eq!(stat_exp_bonus(0), 0);
+        assert_eq!(stat_exp_bonus(100), 2); // sqrt = 10
+        assert_eq!(stat_exp_bonus(226), 4); // ceil(15.03) = 16
+        assert_eq!(stat_exp_bonus(225), 3); // sqrt = 15
+        // ceil(sqrt(65535)) = 256 is capped to 255
+        assert_eq!(stat_exp_bonus(MAX_STAT_EXP), 63);
+        assert_eq!(stat_exp_bonus(u32::MAX), 63);
+    }
+
+    #[test]
+    fn test_gen1_mewtwo() {
+        // Mewtwo: 106 / 110 / 90 / Special 154 / 130 Speed
+        let base = StatBlock::from_array([106, 110, 90, 154, 0, 130]);
+        let stats = calculate_stats_gen12(1, &base, &Dvs::MAX, &StatExp::MAX, 100);
+        assert_eq!(stats.to_array(), [415, 318, 278, 406, 406, 358]);
+    }
+
+    #[test]
+    fn test_gen2_split_special() {
+        // Gen 2 Snorlax: 160 / 110 / 65 / 65 / 110 / 30, no Stat Experience, level 50
+        let base = [160, 110, 65, 65, 110, 30];
+        let stats = calculate_all_stats_gen12(2, &base, &[15, 15, 15, 15], &[0; 5], 50);
+        assert_eq!(stats, vec![235, 130, 85, 85, 130, 50]);
+        // Gen 1 ignores the Sp. Def base
+        let stats = calculate_all_stats_gen12(1, &base, &[15, 15, 15, 15], &[0; 5], 50);
+        assert_eq!(stats[3], stats[4]);
+    }
+
+    #[test]
+    fn test_invalid_gen12_inputs() {
+        let base = [45, 49, 49, 65, 65, 45];
+        assert!(calculate_all_stats_gen12(3, &base, &[15; 4], &[0; 5], 50).is_empty());
+        assert!(calculate_all_stats_gen12(1, &base, &[16, 15, 15, 15], &[0; 5], 50).is_empty());
+        assert!(calculate_all_stats_gen12(1, &base, &[15; 4], &[0; 6], 50).is_empty());
+    }
+}
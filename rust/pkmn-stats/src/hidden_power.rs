@@ -0,0 +1,190 @@
+use pkmn_type_chart::PokemonType;
+use wasm_bindgen::prelude::*;
+
+use crate::gen12::{Dvs, MAX_DV};
+use crate::iv_range::MAX_IV;
+use crate::stat_block::StatBlock;
+
+/// Hidden Power types in formula order (index 0-15). Normal and Fairy are not possible.
+const HIDDEN_POWER_TYPES: [PokemonType; 16] = [
+    PokemonType::Fighting,
+    PokemonType::Flying,
+    PokemonType::Poison,
+    PokemonType::Ground,
+    PokemonType::Rock,
+    PokemonType::Bug,
+    PokemonType::Ghost,
+    PokemonType::Steel,
+    PokemonType::Fire,
+    PokemonType::Water,
+    PokemonType::Grass,
+    PokemonType::Electric,
+    PokemonType::Psychic,
+    PokemonType::Ice,
+    PokemonType::Dragon,
+    PokemonType::Dark,
+];
+
+/// Type and base power of a Pokemon's Hidden Power.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HiddenPower {
+    pub hp_type: PokemonType,
+    pub power: u32,
+}
+
+/// Sum of one bit of each IV, weighted in the formula order
+/// HP, Atk, Def, Spe, SpA, SpD (note Speed before the special stats).
+fn weighted_bits(ivs: &StatBlock, bit: u32) -> u32 {
+    let order = [ivs.hp, ivs.attack, ivs.defense, ivs.speed, ivs.sp_atk, ivs.sp_def];
+    order
+        .iter()
+        .enumerate()
+        .map(|(i, iv)| ((iv >> bit) & 1) << i)
+        .sum()
+}
+
+/// Hidden Power from IVs (Gen 3 onwards).
+///
+/// Type: floor(lowest_bits * 15 / 63), lowest bit of each IV.
+/// Power: floor(second_bits * 40 / 63) + 30 (30-70) in Gens 3-5, and 60 from Gen 6.
+pub fn hidden_power(ivs: &StatBlock, generation: u8) -> HiddenPower {
+    let hp_type = HIDDEN_POWER_TYPES[(weighted_bits(ivs, 0) * 15 / 63) as usize];
+    let power = if generation >= 6 {
+        60
+    } else {
+        weighted_bits(ivs, 1) * 40 / 63 + 30
+    };
+    HiddenPower { hp_type, power }
+}
+
+/// Hidden Power from Gen 2 DVs.
+///
+/// Type: 4 * (atk % 4) + def % 4.
+/// Power: floor((5 * (spc_msb + 2*spe_msb + 4*def_msb + 8*atk_msb) + spc % 4) / 2) + 31 (31-70).
+pub fn hidden_power_gen2(dvs: &Dvs) -> HiddenPower {
+    let hp_type = HIDDEN_POWER_TYPES[(4 * (dvs.attack % 4) + dvs.defense % 4) as usize];
+    let msb = |dv: u32| (dv >> 3) & 1;
+    let msbs = msb(dvs.special) + 2 * msb(dvs.speed) + 4 * msb(dvs.defense) + 8 * msb(dvs.attack);
+    let power = (5 * msbs + dvs.special % 4) / 2 + 31;
+    HiddenPower { hp_type, power }
+}
+
+/// IV spreads that give a Hidden Power of `target` with the fewest IVs
+/// below 31. Every IV in a returned spread is 30 or 31, so the Gen 3-5
+/// power is always 70. Returns an empty list for Normal and Fairy.
+pub fn hidden_power_spreads(target: PokemonType) -> Vec<StatBlock> {
+    let mut spreads: Vec<(u32, StatBlock)> = (0u32..64)
+        .map(|bits| {
+            // Bit i of `bits` is the lowest bit of the i-th IV in [hp, atk, def, spa, spd, spe] order
+            let ivs = StatBlock::from_array(std::array::from_fn(|i| MAX_IV - 1 + ((bits >> i) & 1)));
+            (6 - bits.count_ones(), ivs)
+        })
+        .filter(|(_, ivs)| hidden_power(ivs, 5).hp_type == target)
+        .collect();
+    let Some(fewest) = spreads.iter().map(|(sacrifices, _)| *sacrifices).min() else {
+        return Vec::new();
+    };
+    spreads.retain(|(sacrifices, _)| *sacrifices == fewest);
+    spreads.into_iter().map(|(_, ivs)| ivs).collect()
+}
+
+/// Hidden Power of an IV spread.
+///
+/// Parameters:
+/// - `ivs`: `[hp, atk, def, spa, spd, spe]`, each 0-31
+/// - `generation`: 3-5 for variable power, 6+ for a fixed 60
+///
+/// Returns `[type_idx, power]`, or an empty vector if `ivs` does not have 6 values.
+#[wasm_bindgen]
+pub fn get_hidden_power(ivs: &[u32], generation: u8) -> Vec<u32> {
+    match StatBlock::from_slice(ivs) {
+        Some(ivs) => {
+            let hp = hidden_power(&ivs, generation);
+            vec![hp.hp_type.index() as u32, hp.power]
+        }
+        None => Vec::new(),
+    }
+}
+
+/// Hidden Power from Gen 2 DVs `[atk, def, spe, special]`, each 0-15.
+///
+/// Returns `[type_idx, power]`, or an empty vector on invalid input.
+#[wasm_bindgen]
+pub fn get_hidden_power_gen2(dvs: &[u32]) -> Vec<u32> {
+    let &[attack, defense, speed, special] = dvs else {
+        return Vec::new();
+    };
+    if dvs.iter().any(|&dv| dv > MAX_DV) {
+        return Vec::new();
+    }
+    let hp = hidden_power_gen2(&Dvs { attack, defense, speed, special });
+    vec![hp.hp_type.index() as u32, hp.power]
+}
+
+/// IV spreads giving a Hidden Power of `type_idx` with the fewest IVs below 31.
+///
+/// Returns a flat `Vec<u32>` of 6 IVs per spread in `[hp, atk, def, spa, spd, spe]`
+/// order. Empty for Normal, Fairy or an invalid type index.
+#[wasm_bindgen]
+pub fn get_hidden_power_spreads(type_idx: u8) -> Vec<u32> {
+    let Some(target) = PokemonType::from_index(type_idx) else {
+        return Vec::new();
+    };
+    hidden_power_spreads(target)
+        .iter()
+        .flat_map(|ivs| ivs.to_array())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_perfect_ivs_are_dark() {
+        let hp = hidden_power(&StatBlock::uniform(31), 5);
+        assert_eq!(hp, HiddenPower { hp_type: PokemonType::Dark, power: 70 });
+        assert_eq!(hidden_power(&StatBlock::uniform(31), 7).power, 60);
+        assert_eq!(hidden_power(&StatBlock::uniform(0), 3).hp_type, PokemonType::Fighting);
+        assert_eq!(hidden_power(&StatBlock::uniform(0), 3).power, 30);
+    }
+
+    #[test]
+    fn test_known_spreads() {
+        // HP Fire: 31/30/31/30/31/30
+        let fire = StatBlock::from_array([31, 30, 31, 30, 31, 30]);
+        assert_eq!(hidden_power(&fire, 5).hp_type, PokemonType::Fire);
+        // HP Ice: 31/30/30/31/31/31
+        let ice = StatBlock::from_array([31, 30, 30, 31, 31, 31]);
+        assert_eq!(hidden_power(&ice, 5).hp_type, PokemonType::Ice);
+        assert_eq!(get_hidden_power(&[31, 30, 30, 31, 31, 31], 5), vec![5, 70]);
+    }
+
+    #[test]
+    fn test_fewest_sacrifices() {
+        // HP Ice only needs a 30 Speed IV (31/30/30/31/31/31 sacrifices two)
+        let ice = hidden_power_spreads(PokemonType::Ice);
+        assert_eq!(ice, vec![StatBlock::from_array([31, 31, 31, 31, 31, 30])]);
+        // HP Fire needs three
+        let fire = hidden_power_spreads(PokemonType::Fire);
+        assert!(fire.contains(&StatBlock::from_array([31, 30, 31, 30, 31, 30])));
+        for ivs in &fire {
+            assert_eq!(hidden_power(ivs, 5).hp_type, PokemonType::Fire);
+            assert_eq!(ivs.to_array().iter().filter(|&&iv| iv == 30).count(), 3);
+        }
+        assert_eq!(hidden_power_spreads(PokemonType::Dark), vec![StatBlock::uniform(31)]);
+        assert!(hidden_power_spreads(PokemonType::Normal).is_empty());
+        assert_eq!(get_hidden_power_spreads(PokemonType::Ice.index()).len(), ice.len() * 6);
+    }
+
+    #[test]
+    fn test_gen2_hidden_power() {
+        // Max DVs: Dark, 70 power
+        assert_eq!(get_hidden_power_gen2(&[15, 15, 15, 15]), vec![15, 70]);
+        // Atk 12 / Def 12 (both % 4 = 0): Fighting; all MSBs set, Special % 4 = 3
+        let hp = hidden_power_gen2(&Dvs { attack: 12, defense: 12, speed: 15, special: 15 });
+        assert_eq!(hp, HiddenPower { hp_type: PokemonType::Fighting, power: 70 });
+        assert_eq!(hidden_power_gen2(&Dvs { attack: 0, defense: 0, speed: 0, special: 0 }).power, 31);
+        assert!(get_hidden_power_gen2(&[16, 15, 15, 15]).is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+use wasm_bindgen::prelude::*;
+
+use crate::nature::{Nature, Stat};
+use crate::stat_block::StatBlock;
+use crate::{calculate_hp, calculate_stat};
+
+pub const MAX_IV: u32 = 31;
+
+/// Inclusive range of IVs consistent with an observed stat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IvRange {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl IvRange {
+    /// True when only one IV produces the observed stat.
+    pub fn is_exact(&self) -> bool {
+        self.min == self.max
+    }
+
+    pub fn contains(&self, iv: u32) -> bool {
+        (self.min..=self.max).contains(&iv)
+    }
+}
+
+/// IVs (0-31) that produce `observed` for one stat.
+///
+/// Stats never decrease as the IV grows, so the matches always form a single
+/// range. At low levels several IVs round to the same stat, giving a wide range.
+/// Shedinja (base HP 1) has 1 HP regardless of IV, so any IV matches.
+/// Returns `None` when no IV produces `observed` (wrong EVs, nature or level).
+pub fn iv_range(
+    stat: Stat,
+    base: u32,
+    ev: u32,
+    nature: Nature,
+    level: u32,
+    observed: u32,
+) -> Option<IvRange> {
+    let mut matching = (0..=MAX_IV).filter(|&iv| {
+        let value = match stat {
+            Stat::Hp => calculate_hp(base, iv, ev, level),
+            _ => calculate_stat(base, iv, ev, nature.modifier(stat), level),
+        };
+        value == observed
+    });
+    let min = matching.next()?;
+    let max = matching.next_back().unwrap_or(min);
+    Some(IvRange { min, max })
+}
+
+/// `iv_range` for all six stats.
+pub fn infer_iv_ranges(
+    base: &StatBlock,
+    evs: &StatBlock,
+    nature: Nature,
+    level: u32,
+    observed: &StatBlock,
+) -> [Option<IvRange>; 6] {
+    Stat::ALL.map(|stat| {
+        iv_range(stat, base.get(stat), evs.get(stat), nature, level, observed.get(stat))
+    })
+}
+
+/// Infer the possible IVs of each stat from a Pokemon's displayed stats.
+///
+/// Parameters:
+/// - `base`, `evs`, `observed`: `[hp, atk, def, spa, spd, spe]` arrays
+/// - `nature`: nature name ("adamant") or in-game index ("3")
+/// - `level`: the Pokemon's level
+///
+/// Returns a flat `Vec<i32>` of 6 `[min_iv, max_iv]` pairs in stat order.
+/// A pair is `[-1, -1]` when no IV produces the observed stat.
+/// Returns an empty vector if an array does not have 6 values or the nature is unknown.
+#[wasm_bindgen]
+pub fn calculate_iv_ranges(
+    base: &[u32],
+    evs: &[u32],
+    observed: &[u32],
+    nature: &str,
+    level: u32,
+) -> Vec<i32> {
+    let parsed = (
+        StatBlock::from_slice(base),
+        StatBlock::from_slice(evs),
+        StatBlock::from_slice(observed),
+        nature.parse::<Nature>(),
+    );
+    let (Some(base), Some(evs), Some(observed), Ok(nature)) = parsed else {
+        return Vec::new();
+    };
+
+    infer_iv_ranges(&base, &evs, nature, level, &observed)
+        .iter()
+        .flat_map(|range| match range {
+            Some(r) => [r.min as i32, r.max as i32],
+            None => [-1, -1],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GARCHOMP: [u32; 6] = [108, 130, 95, 80, 85, 102];
+
+    #[test]
+    fn test_exact_ivs_at_level_100() {
+        // Jolly 4/252/0/0/0/252 Garchomp with 31/31/31/31/31/31
+        let observed = [358, 359, 226, 176, 206, 333];
+        let ranges = calculate_iv_ranges(&GARCHOMP, &[4, 252, 0, 0, 0, 252], &observed, "jolly", 100);
+        assert_eq!(ranges, vec![31, 31, 31, 31, 31, 31, 31, 31, 31, 31, 31, 31]);
+    }
+
+    #[test]
+    fn test_ambiguous_ranges() {
+        // At level 50, each stat point covers two IVs
+        let adamant = Nature::from_index(3).unwrap();
+        let range = iv_range(Stat::Def, 95, 0, adamant, 50, 115).unwrap();
+        assert_eq!(range, IvRange { min: 30, max: 31 });
+        assert!(!range.is_exact());
+
+        // At level 5, one stat value covers most of the IV range
+        let range = iv_range(Stat::Spe, 102, 0, adamant, 5, 15).unwrap();
+        assert!(range.contains(0) || range.contains(31));
+        assert!(range.max - range.min >= 10);
+    }
+
+    #[test]
+    fn test_shedinja_hp() {
+        let hardy = Nature::default();
+        assert_eq!(iv_range(Stat::Hp, 1, 0, hardy, 50, 1), Some(IvRange { min: 0, max: 31 }));
+        assert_eq!(iv_range(Stat::Hp, 1, 0, hardy, 50, 2), None);
+    }
+
+    #[test]
+    fn test_impossible_stat() {
+        let ranges = calculate_iv_ranges(&GARCHOMP, &[0; 6], &[999, 1, 150, 101, 105, 122], "hardy", 50);
+        assert!(ranges[..8].iter().all(|&iv| iv == -1));
+        assert_eq!(&ranges[8..10], &[30, 31]);
+        assert!(calculate_iv_ranges(&GARCHOMP, &[0; 6], &[1; 5], "hardy", 50).is_empty());
+    }
+}
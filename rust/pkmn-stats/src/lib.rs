@@ -1,5 +1,47 @@
 use wasm_bindgen::prelude::*;
 
+mod alt_games;
+mod battle_stats;
+mod ev_optimizer;
+mod gen12;
+mod hidden_power;
+mod iv_range;
+mod move_power;
+mod nature;
+mod speed_tiers;
+mod stat_block;
+
+pub use alt_games::{
+    calculate_all_stats_legends, calculate_all_stats_lets_go, calculate_hp_legends,
+    calculate_hp_lets_go, calculate_stat_legends, calculate_stat_lets_go, calculate_stats_legends,
+    calculate_stats_lets_go, effort_level_bonus, lets_go_friendship_bonus, MAX_AV,
+    MAX_EFFORT_LEVEL, MAX_FRIENDSHIP,
+};
+pub use battle_stats::{
+    apply_stat_stage, base_weather, critical_hit_stage, get_battle_speed, BattleStatModifiers,
+    WEATHER_HAIL, WEATHER_HARSH_SUN, WEATHER_HEAVY_RAIN, WEATHER_RAIN, WEATHER_SAND, WEATHER_SNOW,
+    WEATHER_SUN,
+};
+pub use ev_optimizer::{
+    optimize_ev_spread, optimize_evs, EvGoal, OptimizedSpread, MAX_STAT_EVS, MAX_TOTAL_EVS,
+};
+pub use gen12::{
+    calculate_all_stats_gen12, calculate_hp_gen12, calculate_stat_gen12, calculate_stats_gen12,
+    hp_dv_from_dvs, stat_exp_bonus, Dvs, StatExp, MAX_DV, MAX_STAT_EXP,
+};
+pub use hidden_power::{
+    get_hidden_power, get_hidden_power_gen2, get_hidden_power_spreads, hidden_power,
+    hidden_power_gen2, hidden_power_spreads, HiddenPower,
+};
+pub use iv_range::{calculate_iv_ranges, infer_iv_ranges, iv_range, IvRange, MAX_IV};
+pub use move_power::{fixed_damage, variable_power, MoveContext};
+pub use nature::{Nature, ParseNatureError, Stat};
+pub use speed_tiers::{generate_speed_tiers, speed_tiers, SpeedProfile, SpeedTier};
+pub use stat_block::{
+    calculate_all_stats_at_level, calculate_all_stats_with_nature_name, calculate_stat_block,
+    calculate_stats, StatBlock,
+};
+
 /// Calculate HP stat for a Pokemon.
 ///
 /// HP formula: floor(((2*base + iv + floor(ev/4)) * level) / 100) + level + 10
@@ -22,14 +64,17 @@ pub fn calculate_stat(base: u32, iv: u32, ev: u32, nature_modifier: f64, level:
     (raw as f64 * nature_modifier) as u32
 }
 
-/// Calculate all 6 stats at once.
+/// Calculate all 6 stats at once, at level 50.
 ///
 /// Parameters are ordered: bases (hp, atk, def, spa, spd, spe),
 /// IVs (hp, atk, def, spa, spd, spe), EVs (hp, atk, def, spa, spd, spe),
 /// nature modifiers (atk, def, spa, spd, spe). HP has no nature modifier.
 ///
 /// Returns a Vec<u32> of [hp, atk, def, spa, spd, spe].
+/// See `calculate_stats` / `calculate_all_stats_at_level` for other levels
+/// and natures given by name or index.
 #[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
 pub fn calculate_all_stats(
     hp_base: u32,
     atk_base: u32,
@@ -0,0 +1,201 @@
+use pkmn_type_chart::normalize_name;
+use wasm_bindgen::prelude::*;
+
+/// Battle state that decides the power of variable-power moves and the
+/// damage of fixed-damage moves.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MoveContext {
+    pub attacker_level: u32,
+    pub attacker_hp: u32,
+    pub attacker_max_hp: u32,
+    pub defender_hp: u32,
+    pub defender_max_hp: u32,
+    /// Speeds in battle, with stages and modifiers applied.
+    pub attacker_speed: u32,
+    pub defender_speed: u32,
+    /// Weights in hectograms (0.1 kg), as in PokeAPI.
+    pub attacker_weight: u32,
+    pub defender_weight: u32,
+}
+
+#[wasm_bindgen]
+impl MoveContext {
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        attacker_level: u32,
+        attacker_hp: u32,
+        attacker_max_hp: u32,
+        defender_hp: u32,
+        defender_max_hp: u32,
+        attacker_speed: u32,
+        defender_speed: u32,
+        attacker_weight: u32,
+        defender_weight: u32,
+    ) -> MoveContext {
+        MoveContext {
+            attacker_level,
+            attacker_hp,
+            attacker_max_hp,
+            defender_hp,
+            defender_max_hp,
+            attacker_speed,
+            defender_speed,
+            attacker_weight,
+            defender_weight,
+        }
+    }
+}
+
+/// Base power of a move whose power depends on the battle state, or `None`
+/// for any other move.
+///
+/// - Low Kick / Grass Knot: 20-120 by the target's weight
+/// - Heavy Slam / Heat Crash: 40-120 by the user's weight over the target's
+/// - Gyro Ball: 25 * target Speed / user Speed + 1, up to 150
+/// - Electro Ball: 40-150 by the user's Speed over the target's, 40 against
+///   a target at 0 Speed
+/// - Eruption / Water Spout / Dragon Energy: 150 * HP / max HP, at least 1
+/// - Reversal / Flail: 20-200, higher at lower HP
+pub fn variable_power(move_name: &str, ctx: &MoveContext) -> Option<u32> {
+    let power = match normalize_name(move_name).as_str() {
+        "lowkick" | "grassknot" => match ctx.defender_weight {
+            0..=99 => 20,
+            100..=249 => 40,
+            250..=499 => 60,
+            500..=999 => 80,
+            1000..=1999 => 100,
+            _ => 120,
+        },
+        "heavyslam" | "heatcrash" => match ctx.attacker_weight / ctx.defender_weight.max(1) {
+            5.. => 120,
+            4 => 100,
+            3 => 80,
+            2 => 60,
+            _ => 40,
+        },
+        "gyroball" => (25 * ctx.defender_speed)
+            .checked_div(ctx.attacker_speed)
+            .map_or(1, |ratio| (ratio + 1).min(150)),
+        "electroball" => match ctx.attacker_speed.checked_div(ctx.defender_speed) {
+            Some(4..) => 150,
+            Some(3) => 120,
+            Some(2) => 80,
+            Some(1) => 60,
+            // A target at 0 Speed gives the minimum power
+            Some(_) | None => 40,
+        },
+        "eruption" | "waterspout" | "dragonenergy" => {
+            (150 * ctx.attacker_hp / ctx.attacker_max_hp.max(1)).max(1)
+        }
+        "reversal" | "flail" => match 48 * ctx.attacker_hp / ctx.attacker_max_hp.max(1) {
+            0..=1 => 200,
+            2..=4 => 150,
+            5..=9 => 100,
+            10..=16 => 80,
+            17..=32 => 40,
+            _ => 20,
+        },
+        _ => return None,
+    };
+    Some(power)
+}
+
+/// Damage of a fixed-damage move, or `None` for any other move. Only type
+/// immunity still applies to these moves.
+///
+/// - Seismic Toss / Night Shade: the user's level
+/// - Super Fang / Nature's Madness / Ruination: half the target's current HP, at least 1
+/// - Final Gambit: the user's current HP
+/// - Endeavor: the target's HP above the user's
+/// - Dragon Rage: 40, Sonic Boom: 20
+pub fn fixed_damage(move_name: &str, ctx: &MoveContext) -> Option<u32> {
+    let damage = match normalize_name(move_name).as_str() {
+        "seismictoss" | "nightshade" => ctx.attacker_level,
+        "superfang" | "naturesmadness" | "ruination" => (ctx.defender_hp / 2).max(1),
+        "finalgambit" => ctx.attacker_hp,
+        "endeavor" => ctx.defender_hp.saturating_sub(ctx.attacker_hp),
+        "dragonrage" => 40,
+        "sonicboom" => 20,
+        _ => return None,
+    };
+    Some(damage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx() -> MoveContext {
+        MoveContext::new(50, 100, 100, 150, 200, 100, 100, 1000, 1000)
+    }
+
+    #[test]
+    fn test_weight_based_power() {
+        let mut c = ctx();
+        assert_eq!(variable_power("Low Kick", &c), Some(100));
+        c.defender_weight = 99;
+        assert_eq!(variable_power("grass-knot", &c), Some(20));
+        c.defender_weight = 2000;
+        assert_eq!(variable_power("Low Kick", &c), Some(120));
+
+        c.attacker_weight = 10000;
+        assert_eq!(variable_power("Heavy Slam", &c), Some(120));
+        c.attacker_weight = 5999;
+        assert_eq!(variable_power("Heat Crash", &c), Some(60));
+        c.defender_weight = 0;
+        assert_eq!(variable_power("Heavy Slam", &c), Some(120));
+    }
+
+    #[test]
+    fn test_speed_based_power() {
+        let mut c = ctx();
+        c.attacker_speed = 50;
+        c.defender_speed = 200;
+        assert_eq!(variable_power("Gyro Ball", &c), Some(101));
+        c.attacker_speed = 5;
+        assert_eq!(variable_power("Gyro Ball", &c), Some(150));
+        c.attacker_speed = 0;
+        assert_eq!(variable_power("Gyro Ball", &c), Some(1));
+
+        c.attacker_speed = 601;
+        assert_eq!(variable_power("Electro Ball", &c), Some(120));
+        c.attacker_speed = 199;
+        assert_eq!(variable_power("Electro Ball", &c), Some(40));
+        c.defender_speed = 0;
+        assert_eq!(variable_power("Electro Ball", &c), Some(40));
+    }
+
+    #[test]
+    fn test_hp_based_power() {
+        let mut c = ctx();
+        assert_eq!(variable_power("Eruption", &c), Some(150));
+        c.attacker_hp = 50;
+        assert_eq!(variable_power("Water Spout", &c), Some(75));
+        assert_eq!(variable_power("Reversal", &c), Some(40));
+        c.attacker_hp = 1;
+        assert_eq!(variable_power("Eruption", &c), Some(1));
+        assert_eq!(variable_power("Flail", &c), Some(200));
+        c.attacker_hp = 20;
+        assert_eq!(variable_power("Reversal", &c), Some(100));
+        assert_eq!(variable_power("Tackle", &c), None);
+    }
+
+    #[test]
+    fn test_fixed_damage() {
+        let c = ctx();
+        assert_eq!(fixed_damage("Seismic Toss", &c), Some(50));
+        assert_eq!(fixed_damage("Super Fang", &c), Some(75));
+        assert_eq!(fixed_damage("Final Gambit", &c), Some(100));
+        assert_eq!(fixed_damage("Endeavor", &c), Some(50));
+        assert_eq!(fixed_damage("Dragon Rage", &c), Some(40));
+        assert_eq!(fixed_damage("Earthquake", &c), None);
+        let low = MoveContext {
+            defender_hp: 1,
+            ..c
+        };
+        assert_eq!(fixed_damage("Super Fang", &low), Some(1));
+        assert_eq!(fixed_damage("Endeavor", &low), Some(0));
+    }
+}
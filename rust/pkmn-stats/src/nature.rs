@@ -0,0 +1,159 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// The six stats, in the `[hp, atk, def, spa, spd, spe]` order used by every
+/// flat stat array in this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stat {
+    Hp = 0,
+    Atk = 1,
+    Def = 2,
+    SpA = 3,
+    SpD = 4,
+    Spe = 5,
+}
+
+impl Stat {
+    pub const ALL: [Stat; 6] = [Stat::Hp, Stat::Atk, Stat::Def, Stat::SpA, Stat::SpD, Stat::Spe];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Nature names in in-game index order (personality value % 25), the same
+/// order as `NATURE_ORDER` in `gen3ToAppMapper.ts`.
+const NATURE_NAMES: [&str; 25] = [
+    "hardy", "lonely", "brave", "adamant", "naughty",
+    "bold", "docile", "relaxed", "impish", "lax",
+    "timid", "hasty", "serious", "jolly", "naive",
+    "modest", "mild", "quiet", "bashful", "rash",
+    "calm", "gentle", "sassy", "careful", "quirky",
+];
+
+/// Stats affected by natures, in the order the in-game index encodes them:
+/// the boosted stat is `index / 5` and the hindered stat is `index % 5`.
+const NATURE_STATS: [Stat; 5] = [Stat::Atk, Stat::Def, Stat::Spe, Stat::SpA, Stat::SpD];
+
+/// One of the 25 natures, identified by its in-game index (0 = Hardy, 24 = Quirky).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Nature(u8);
+
+impl Nature {
+    pub const COUNT: u8 = 25;
+
+    /// Nature with the given in-game index, or `None` if it is 25 or higher.
+    pub fn from_index(index: u8) -> Option<Nature> {
+        (index < Self::COUNT).then_some(Nature(index))
+    }
+
+    pub fn index(self) -> u8 {
+        self.0
+    }
+
+    /// Lowercase name ("adamant").
+    pub fn name(self) -> &'static str {
+        NATURE_NAMES[self.0 as usize]
+    }
+
+    /// Boosted stat, or `None` for the five neutral natures.
+    pub fn increased(self) -> Option<Stat> {
+        let (up, down) = (self.0 / 5, self.0 % 5);
+        (up != down).then(|| NATURE_STATS[up as usize])
+    }
+
+    /// Hindered stat, or `None` for the five neutral natures.
+    pub fn decreased(self) -> Option<Stat> {
+        let (up, down) = (self.0 / 5, self.0 % 5);
+        (up != down).then(|| NATURE_STATS[down as usize])
+    }
+
+    /// 1.1 for the boosted stat, 0.9 for the hindered stat, 1.0 otherwise (always for HP).
+    pub fn modifier(self, stat: Stat) -> f64 {
+        if self.increased() == Some(stat) {
+            1.1
+        } else if self.decreased() == Some(stat) {
+            0.9
+        } else {
+            1.0
+        }
+    }
+}
+
+impl fmt::Display for Nature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.name();
+        f.write_str(&name[..1].to_ascii_uppercase())?;
+        f.write_str(&name[1..])
+    }
+}
+
+/// Error returned when a string is neither a nature name nor an index below 25.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseNatureError(pub String);
+
+impl fmt::Display for ParseNatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown nature: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseNatureError {}
+
+impl FromStr for Nature {
+    type Err = ParseNatureError;
+
+    /// Parse a nature name ("Adamant", "adamant") or its in-game index ("3").
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_ascii_lowercase();
+        let by_name = NATURE_NAMES.iter().position(|&n| n == lower).map(|i| Nature(i as u8));
+        by_name
+            .or_else(|| lower.parse().ok().and_then(Nature::from_index))
+            .ok_or_else(|| ParseNatureError(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nature_stats() {
+        let adamant: Nature = "Adamant".parse().unwrap();
+        assert_eq!(adamant.index(), 3);
+        assert_eq!(adamant.increased(), Some(Stat::Atk));
+        assert_eq!(adamant.decreased(), Some(Stat::SpA));
+        assert_eq!(adamant.modifier(Stat::Atk), 1.1);
+        assert_eq!(adamant.modifier(Stat::SpA), 0.9);
+        assert_eq!(adamant.modifier(Stat::Hp), 1.0);
+
+        let timid = Nature::from_index(10).unwrap();
+        assert_eq!(timid.name(), "timid");
+        assert_eq!(timid.increased(), Some(Stat::Spe));
+        assert_eq!(timid.decreased(), Some(Stat::Atk));
+    }
+
+    #[test]
+    fn test_neutral_natures() {
+        for name in ["hardy", "docile", "serious", "bashful", "quirky"] {
+            let nature: Nature = name.parse().unwrap();
+            assert_eq!(nature.increased(), None);
+            assert!(Stat::ALL.iter().all(|&s| nature.modifier(s) == 1.0));
+        }
+        let boosting = (0..Nature::COUNT)
+            .filter_map(Nature::from_index)
+            .filter(|n| n.increased().is_some())
+            .count();
+        assert_eq!(boosting, 20);
+    }
+
+    #[test]
+    fn test_nature_parsing() {
+        assert_eq!("24".parse(), Ok(Nature(24)));
+        assert_eq!(" JOLLY ".parse(), Ok(Nature(13)));
+        assert!("25".parse::<Nature>().is_err());
+        assert!("grumpy".parse::<Nature>().is_err());
+        assert_eq!(Nature::from_index(25), None);
+        assert_eq!(Nature(15).to_string(), "Modest");
+    }
+}
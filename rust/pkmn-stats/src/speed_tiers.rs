@@ -0,0 +1,169 @@
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::calculate_stat;
+
+/// A standard Speed investment used as a benchmark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpeedProfile {
+    /// 252 EVs, 31 IV, +Spe nature.
+    MaxPositive = 0,
+    /// 252 EVs, 31 IV, neutral nature.
+    MaxNeutral = 1,
+    /// 0 EVs, 31 IV, neutral nature.
+    Uninvested = 2,
+    /// 0 EVs, 31 IV, -Spe nature.
+    MinNegative = 3,
+    /// Max+ holding a Choice Scarf (1.5x).
+    Scarf = 4,
+    /// Max+ under Tailwind (2x).
+    Tailwind = 5,
+    /// Max+ at +1 Speed.
+    PlusOne = 6,
+    /// Max+ at +2 Speed.
+    PlusTwo = 7,
+    /// 0 EVs, 0 IV, -Spe nature: the slowest possible, for Trick Room.
+    TrickRoomMin = 8,
+}
+
+impl SpeedProfile {
+    pub const ALL: [SpeedProfile; 9] = [
+        SpeedProfile::MaxPositive,
+        SpeedProfile::MaxNeutral,
+        SpeedProfile::Uninvested,
+        SpeedProfile::MinNegative,
+        SpeedProfile::Scarf,
+        SpeedProfile::Tailwind,
+        SpeedProfile::PlusOne,
+        SpeedProfile::PlusTwo,
+        SpeedProfile::TrickRoomMin,
+    ];
+
+    pub fn from_u8(value: u8) -> Option<SpeedProfile> {
+        Self::ALL.get(value as usize).copied()
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SpeedProfile::MaxPositive => "Max+",
+            SpeedProfile::MaxNeutral => "Max",
+            SpeedProfile::Uninvested => "Uninvested",
+            SpeedProfile::MinNegative => "Min-",
+            SpeedProfile::Scarf => "Max+ Scarf",
+            SpeedProfile::Tailwind => "Max+ Tailwind",
+            SpeedProfile::PlusOne => "Max+ +1",
+            SpeedProfile::PlusTwo => "Max+ +2",
+            SpeedProfile::TrickRoomMin => "Trick Room min",
+        }
+    }
+
+    /// Final Speed of a Pokemon with `base_speed` at `level` under this profile.
+    pub fn speed(self, base_speed: u32, level: u32) -> u32 {
+        let stat = |iv, ev, nature| calculate_stat(base_speed, iv, ev, nature, level);
+        let max_positive = stat(31, 252, 1.1);
+        match self {
+            SpeedProfile::MaxPositive => max_positive,
+            SpeedProfile::MaxNeutral => stat(31, 252, 1.0),
+            SpeedProfile::Uninvested => stat(31, 0, 1.0),
+            SpeedProfile::MinNegative => stat(31, 0, 0.9),
+            SpeedProfile::Scarf | SpeedProfile::PlusOne => max_positive * 3 / 2,
+            SpeedProfile::Tailwind | SpeedProfile::PlusTwo => max_positive * 2,
+            SpeedProfile::TrickRoomMin => stat(0, 0, 0.9),
+        }
+    }
+}
+
+/// One row of a speed tier table: a Speed value and every benchmark that reaches it.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SpeedTier {
+    pub speed: u32,
+    /// Labels such as "Base 102 Max+ Scarf", in input order.
+    pub labels: Vec<String>,
+}
+
+/// Build a speed tier table for every base Speed under every profile.
+///
+/// Rows are sorted fastest first, with one row per distinct Speed value.
+/// Repeated base Speeds are only listed once.
+pub fn speed_tiers(base_speeds: &[u32], profiles: &[SpeedProfile], level: u32) -> Vec<SpeedTier> {
+    let mut tiers: Vec<SpeedTier> = Vec::new();
+    for (i, &base) in base_speeds.iter().enumerate() {
+        if base_speeds[..i].contains(&base) {
+            continue;
+        }
+        for &profile in profiles {
+            let speed = profile.speed(base, level);
+            let label = format!("Base {} {}", base, profile.label());
+            match tiers.iter_mut().find(|t| t.speed == speed) {
+                Some(tier) => {
+                    if !tier.labels.contains(&label) {
+                        tier.labels.push(label);
+                    }
+                }
+                None => tiers.push(SpeedTier { speed, labels: vec![label] }),
+            }
+        }
+    }
+    tiers.sort_by_key(|t| std::cmp::Reverse(t.speed));
+    tiers
+}
+
+/// Generate a speed tier table.
+///
+/// Parameters:
+/// - `base_speeds`: base Speed stats to chart
+/// - `profiles`: profile ids (0 = Max+, 1 = Max, 2 = Uninvested, 3 = Min-,
+///   4 = Scarf, 5 = Tailwind, 6 = +1, 7 = +2, 8 = Trick Room min).
+///   Unknown ids are ignored; an empty array uses every profile.
+/// - `level`: level of every Pokemon in the table
+///
+/// Returns an array of `{ speed, labels }` rows sorted fastest first,
+/// one row per distinct Speed value.
+#[wasm_bindgen]
+pub fn generate_speed_tiers(base_speeds: &[u32], profiles: &[u8], level: u32) -> JsValue {
+    let profiles: Vec<SpeedProfile> = if profiles.is_empty() {
+        SpeedProfile::ALL.to_vec()
+    } else {
+        profiles.iter().filter_map(|&p| SpeedProfile::from_u8(p)).collect()
+    };
+    let tiers = speed_tiers(base_speeds, &profiles, level);
+    serde_wasm_bindgen::to_value(&tiers).unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_speeds_level_50() {
+        // Garchomp, base 102
+        assert_eq!(SpeedProfile::MaxPositive.speed(102, 50), 169);
+        assert_eq!(SpeedProfile::MaxNeutral.speed(102, 50), 154);
+        assert_eq!(SpeedProfile::Uninvested.speed(102, 50), 122);
+        assert_eq!(SpeedProfile::MinNegative.speed(102, 50), 109);
+        assert_eq!(SpeedProfile::Scarf.speed(102, 50), 253);
+        assert_eq!(SpeedProfile::Tailwind.speed(102, 50), 338);
+        assert_eq!(SpeedProfile::TrickRoomMin.speed(102, 50), 96);
+    }
+
+    #[test]
+    fn test_table_sorted_and_deduplicated() {
+        let tiers = speed_tiers(&[102, 30, 102], &SpeedProfile::ALL, 50);
+        assert!(tiers.windows(2).all(|w| w[0].speed > w[1].speed));
+        // Scarf and +1 share a row
+        let scarf = tiers.iter().find(|t| t.speed == 253).unwrap();
+        assert_eq!(scarf.labels, vec!["Base 102 Max+ Scarf", "Base 102 Max+ +1"]);
+        // Repeated base Speed is not listed twice
+        let labels: usize = tiers.iter().map(|t| t.labels.len()).sum();
+        assert_eq!(labels, 2 * SpeedProfile::ALL.len());
+        assert_eq!(tiers.last().unwrap().labels, vec!["Base 30 Trick Room min"]);
+    }
+
+    #[test]
+    fn test_profile_ids() {
+        assert_eq!(SpeedProfile::from_u8(8), Some(SpeedProfile::TrickRoomMin));
+        assert_eq!(SpeedProfile::from_u8(9), None);
+        let tiers = speed_tiers(&[100], &[SpeedProfile::MaxPositive], 100);
+        assert_eq!(tiers, vec![SpeedTier { speed: 328, labels: vec!["Base 100 Max+".to_string()] }]);
+    }
+}
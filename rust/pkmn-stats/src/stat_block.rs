@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::nature::{Nature, Stat};
+use crate::{calculate_hp, calculate_stat};
+
+/// One value per stat. Serializes with the same field names as the TS
+/// `BaseStats` / `StatSpread` interfaces (`hp`, `attack`, ..., `spAtk`, `speed`).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StatBlock {
+    pub hp: u32,
+    pub attack: u32,
+    pub defense: u32,
+    pub sp_atk: u32,
+    pub sp_def: u32,
+    pub speed: u32,
+}
+
+impl StatBlock {
+    /// Build from a `[hp, atk, def, spa, spd, spe]` array.
+    pub fn from_array(values: [u32; 6]) -> StatBlock {
+        let [hp, attack, defense, sp_atk, sp_def, speed] = values;
+        StatBlock { hp, attack, defense, sp_atk, sp_def, speed }
+    }
+
+    /// Build from a slice in `[hp, atk, def, spa, spd, spe]` order.
+    /// Returns `None` unless the slice has exactly 6 values.
+    pub fn from_slice(values: &[u32]) -> Option<StatBlock> {
+        <[u32; 6]>::try_from(values).ok().map(Self::from_array)
+    }
+
+    /// The same value for every stat (e.g. 31 IVs).
+    pub fn uniform(value: u32) -> StatBlock {
+        Self::from_array([value; 6])
+    }
+
+    pub fn to_array(self) -> [u32; 6] {
+        [self.hp, self.attack, self.defense, self.sp_atk, self.sp_def, self.speed]
+    }
+
+    pub fn get(&self, stat: Stat) -> u32 {
+        self.to_array()[stat.index()]
+    }
+}
+
+/// Calculate all six stats at any level.
+pub fn calculate_stats(
+    base: &StatBlock,
+    ivs: &StatBlock,
+    evs: &StatBlock,
+    nature: Nature,
+    level: u32,
+) -> StatBlock {
+    let mut values = [0; 6];
+    for stat in Stat::ALL {
+        let (b, iv, ev) = (base.get(stat), ivs.get(stat), evs.get(stat));
+        values[stat.index()] = match stat {
+            Stat::Hp => calculate_hp(b, iv, ev, level),
+            _ => calculate_stat(b, iv, ev, nature.modifier(stat), level),
+        };
+    }
+    StatBlock::from_array(values)
+}
+
+/// Calculate all 6 stats at any level with a nature given by in-game index
+/// (0 = Hardy ... 24 = Quirky, i.e. personality value % 25).
+///
+/// `base`, `ivs` and `evs` are `[hp, atk, def, spa, spd, spe]` arrays.
+/// Returns `[hp, atk, def, spa, spd, spe]`, or an empty vector if an array
+/// does not have 6 values or the nature index is out of range.
+#[wasm_bindgen]
+pub fn calculate_all_stats_at_level(
+    base: &[u32],
+    ivs: &[u32],
+    evs: &[u32],
+    nature_index: u8,
+    level: u32,
+) -> Vec<u32> {
+    let Some(nature) = Nature::from_index(nature_index) else {
+        return Vec::new();
+    };
+    stats_from_slices(base, ivs, evs, nature, level)
+}
+
+/// `calculate_all_stats_at_level` with a nature name ("adamant", "Timid").
+/// Returns an empty vector for unknown names.
+#[wasm_bindgen]
+pub fn calculate_all_stats_with_nature_name(
+    base: &[u32],
+    ivs: &[u32],
+    evs: &[u32],
+    nature: &str,
+    level: u32,
+) -> Vec<u32> {
+    let Ok(nature) = nature.parse() else {
+        return Vec::new();
+    };
+    stats_from_slices(base, ivs, evs, nature, level)
+}
+
+/// Object form of `calculate_all_stats_with_nature_name`: `base`, `ivs` and
+/// `evs` are `{ hp, attack, defense, spAtk, spDef, speed }` objects.
+/// Returns an object of the same shape, or `null` on invalid input.
+#[wasm_bindgen]
+pub fn calculate_stat_block(
+    base: JsValue,
+    ivs: JsValue,
+    evs: JsValue,
+    nature: &str,
+    level: u32,
+) -> JsValue {
+    let parsed = (
+        serde_wasm_bindgen::from_value::<StatBlock>(base),
+        serde_wasm_bindgen::from_value::<StatBlock>(ivs),
+        serde_wasm_bindgen::from_value::<StatBlock>(evs),
+        nature.parse::<Nature>(),
+    );
+    match parsed {
+        (Ok(base), Ok(ivs), Ok(evs), Ok(nature)) => {
+            let stats = calculate_stats(&base, &ivs, &evs, nature, level);
+            serde_wasm_bindgen::to_value(&stats).unwrap_or(JsValue::NULL)
+        }
+        _ => JsValue::NULL,
+    }
+}
+
+fn stats_from_slices(base: &[u32], ivs: &[u32], evs: &[u32], nature: Nature, level: u32) -> Vec<u32> {
+    match (
+        StatBlock::from_slice(base),
+        StatBlock::from_slice(ivs),
+        StatBlock::from_slice(evs),
+    ) {
+        (Some(base), Some(ivs), Some(evs)) => {
+            calculate_stats(&base, &ivs, &evs, nature, level).to_array().to_vec()
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Garchomp: 108 / 130 / 95 / 80 / 85 / 102
+    const GARCHOMP: [u32; 6] = [108, 130, 95, 80, 85, 102];
+
+    #[test]
+    fn test_level_100_jolly() {
+        let base = StatBlock::from_array(GARCHOMP);
+        let evs = StatBlock::from_array([4, 252, 0, 0, 0, 252]);
+        let jolly = "jolly".parse().unwrap();
+        let stats = calculate_stats(&base, &StatBlock::uniform(31), &evs, jolly, 100);
+        assert_eq!(stats.to_array(), [358, 359, 226, 176, 206, 333]);
+    }
+
+    #[test]
+    fn test_level_50_matches_legacy() {
+        let base = StatBlock::from_array(GARCHOMP);
+        let evs = StatBlock::from_array([4, 252, 0, 0, 0, 252]);
+        let adamant = Nature::from_index(3).unwrap();
+        let stats = calculate_stats(&base, &StatBlock::uniform(31), &evs, adamant, 50);
+        let legacy = crate::calculate_all_stats(
+            108, 130, 95, 80, 85, 102,
+            31, 31, 31, 31, 31, 31,
+            4, 252, 0, 0, 0, 252,
+            1.1, 1.0, 0.9, 1.0, 1.0,
+        );
+        assert_eq!(stats.to_array().to_vec(), legacy);
+    }
+
+    #[test]
+    fn test_little_cup_level_5() {
+        // Mienfoo: 45 / 85 / 50 / 55 / 50 / 65, Jolly 196 Atk / 196 Spe
+        let base = [45, 85, 50, 55, 50, 65];
+        let evs = [0, 196, 36, 0, 36, 196];
+        let result = calculate_all_stats_with_nature_name(&base, &[31; 6], &evs, "Jolly", 5);
+        assert_eq!(result, vec![21, 17, 12, 10, 12, 16]);
+        assert_eq!(calculate_all_stats_at_level(&base, &[31; 6], &evs, 13, 5), result);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(calculate_all_stats_at_level(&[1; 5], &[31; 6], &[0; 6], 0, 50).is_empty());
+        assert!(calculate_all_stats_at_level(&[1; 6], &[31; 6], &[0; 6], 25, 50).is_empty());
+        assert!(calculate_all_stats_with_nature_name(&[1; 6], &[31; 6], &[0; 6], "grumpy", 50).is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+use wasm_bindgen::prelude::*;
+
+use crate::overrides::{effectiveness_in_matrix, EffectivenessContext, EffectivenessResult};
+use crate::{matrix_for_generation, NUM_TYPES};
+
+/// An owned 18x18 effectiveness chart.
+///
+/// Use this instead of the free functions when the rules differ from the
+/// standard chart: older generations, Inverse Battles, or house-rule charts
+/// supplied by the caller. The damage and analysis crates accept a
+/// `&TypeChart` in their `*_with_chart` entry points.
+#[wasm_bindgen]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeChart {
+    matrix: [f64; NUM_TYPES * NUM_TYPES],
+}
+
+impl Default for TypeChart {
+    fn default() -> Self {
+        Self::for_generation(0)
+    }
+}
+
+#[wasm_bindgen]
+impl TypeChart {
+    /// The standard chart of a generation (see `matrix_for_generation`).
+    pub fn for_generation(generation: u8) -> TypeChart {
+        TypeChart {
+            matrix: *matrix_for_generation(generation),
+        }
+    }
+
+    /// The Inverse Battle chart of a generation: super effective matchups
+    /// become 0.5x, and resisted or immune matchups become 2x.
+    pub fn inverse(generation: u8) -> TypeChart {
+        Self::for_generation(generation).inverted()
+    }
+
+    /// Build a chart from a caller-supplied flat matrix, where
+    /// `values[atk * 18 + def]` is the multiplier.
+    /// Returns `None` unless exactly 324 finite, non-negative values are given.
+    pub fn from_matrix(values: &[f64]) -> Option<TypeChart> {
+        if values.len() != NUM_TYPES * NUM_TYPES || values.iter().any(|v| !v.is_finite() || *v < 0.0) {
+            return None;
+        }
+        let mut matrix = [0.0; NUM_TYPES * NUM_TYPES];
+        matrix.copy_from_slice(values);
+        Some(TypeChart { matrix })
+    }
+
+    /// Swap super effective and not very effective entries of this chart.
+    pub fn inverted(&self) -> TypeChart {
+        let mut matrix = self.matrix;
+        for m in matrix.iter_mut() {
+            *m = if *m > 1.0 {
+                0.5
+            } else if *m < 1.0 {
+                2.0
+            } else {
+                1.0
+            };
+        }
+        TypeChart { matrix }
+    }
+
+    /// Effectiveness of `atk_type` vs a single `def_type`.
+    /// Returns 1.0 (neutral) for out-of-range indices.
+    pub fn effectiveness(&self, atk_type: u8, def_type: u8) -> f64 {
+        let a = atk_type as usize;
+        let d = def_type as usize;
+        if a >= NUM_TYPES || d >= NUM_TYPES {
+            return 1.0;
+        }
+        self.matrix[a * NUM_TYPES + d]
+    }
+
+    /// Combined multiplier vs a dual-type defender.
+    /// `def_type2` is -1 for single-type Pokemon.
+    pub fn defensive_multiplier(&self, atk_type: u8, def_type1: u8, def_type2: i8) -> f64 {
+        let mut mult = self.effectiveness(atk_type, def_type1);
+        if def_type2 >= 0 {
+            mult *= self.effectiveness(atk_type, def_type2 as u8);
+        }
+        mult
+    }
+
+    /// The flat 324-entry matrix, row-major by attacking type.
+    pub fn to_vec(&self) -> Vec<f64> {
+        self.matrix.to_vec()
+    }
+}
+
+impl TypeChart {
+    pub fn matrix(&self) -> &[f64; NUM_TYPES * NUM_TYPES] {
+        &self.matrix
+    }
+
+    /// `effectiveness_with_context`, evaluated against this chart.
+    pub fn effectiveness_with_context(
+        &self,
+        atk_type: u8,
+        def_type1: u8,
+        def_type2: i8,
+        ctx: &EffectivenessContext,
+    ) -> EffectivenessResult {
+        effectiveness_in_matrix(&self.matrix, atk_type, def_type1, def_type2, ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_defensive_multiplier, get_effectiveness};
+
+    #[test]
+    fn test_default_matches_free_functions() {
+        let chart = TypeChart::default();
+        for atk in 0..NUM_TYPES as u8 {
+            for def in 0..NUM_TYPES as u8 {
+                assert_eq!(chart.effectiveness(atk, def), get_effectiveness(atk, def));
+            }
+        }
+        assert_eq!(chart.defensive_multiplier(8, 1, 16), get_defensive_multiplier(8, 1, 16));
+    }
+
+    #[test]
+    fn test_inverse_chart() {
+        let chart = TypeChart::inverse(6);
+        assert_eq!(chart.effectiveness(1, 4), 0.5); // fire vs grass
+        assert_eq!(chart.effectiveness(1, 2), 2.0); // fire vs water
+        assert_eq!(chart.effectiveness(0, 13), 2.0); // normal vs ghost
+        assert_eq!(chart.effectiveness(0, 1), 1.0); // normal vs fire
+        // Ground vs Fire/Steel: 0.5 * 0.5
+        assert_eq!(chart.defensive_multiplier(8, 1, 16), 0.25);
+    }
+
+    #[test]
+    fn test_inverse_keeps_absent_types_neutral() {
+        let chart = TypeChart::inverse(1);
+        assert_eq!(chart.effectiveness(14, 17), 1.0); // no Fairy in gen 1
+        assert_eq!(chart.effectiveness(13, 10), 2.0); // gen 1 ghost vs psychic flipped
+    }
+
+    #[test]
+    fn test_from_matrix() {
+        let mut values = vec![1.0; NUM_TYPES * NUM_TYPES];
+        values[NUM_TYPES + 2] = 4.0; // fire vs water
+        let chart = TypeChart::from_matrix(&values).expect("valid matrix");
+        assert_eq!(chart.effectiveness(1, 2), 4.0);
+        assert_eq!(chart.effectiveness(1, 4), 1.0);
+        assert_eq!(chart.to_vec(), values);
+    }
+
+    #[test]
+    fn test_from_matrix_rejects_bad_input() {
+        assert!(TypeChart::from_matrix(&[1.0; 10]).is_none());
+        let mut values = vec![1.0; NUM_TYPES * NUM_TYPES];
+        values[0] = -1.0;
+        assert!(TypeChart::from_matrix(&values).is_none());
+        values[0] = f64::NAN;
+        assert!(TypeChart::from_matrix(&values).is_none());
+    }
+
+    #[test]
+    fn test_context_query_uses_chart() {
+        let chart = TypeChart::inverse(6);
+        let ctx = EffectivenessContext {
+            defender_ability: "levitate",
+            ..Default::default()
+        };
+        // Ground vs Flying is 2x in an Inverse Battle, but Levitate still blocks
+        let r = chart.effectiveness_with_context(8, 9, -1, &EffectivenessContext::default());
+        assert_eq!(r.multiplier, 2.0);
+        let r = chart.effectiveness_with_context(8, 1, -1, &ctx);
+        assert_eq!(r.multiplier, 0.0);
+    }
+}
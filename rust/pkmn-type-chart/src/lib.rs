@@ -1,14 +1,51 @@
+mod chart;
+mod overrides;
+mod profile;
+mod quiz;
+mod rng;
+mod tera;
+mod types;
+
 use wasm_bindgen::prelude::*;
 
+pub use chart::TypeChart;
+pub use overrides::{
+    effectiveness_with_context, get_effectiveness_with_context, normalize_name,
+    EffectivenessContext, EffectivenessResult, ImmunityReason, FIELD_DEFENDER_GROUNDED,
+    FIELD_GRAVITY, FIELD_MAGIC_ROOM, MOVE_FREEZE_DRY, MOVE_THOUSAND_ARROWS,
+};
+pub use types::{
+    defensive_multiplier, effectiveness, get_defensive_multiplier_by_name,
+    get_effectiveness_by_name, type_from_name, type_name, ParseTypeError, PokemonType,
+};
+pub use profile::{
+    all_type_combinations, get_defensive_profile, get_offensive_profile, DefensiveProfile,
+    OffensiveEntry, OffensiveProfile, NUM_TYPE_COMBINATIONS,
+};
+pub use quiz::{generate_quiz, generate_type_quiz, QuizDifficulty, QuizQuestion};
+pub use tera::{
+    get_tera_defensive_multiplier, get_tera_stab_multiplier, tera_defensive_types, STELLAR,
+};
+
 /// The 18 Pokemon types in order:
 ///  0 = Normal,  1 = Fire,     2 = Water,    3 = Electric,
 ///  4 = Grass,   5 = Ice,      6 = Fighting, 7 = Poison,
 ///  8 = Ground,  9 = Flying,  10 = Psychic, 11 = Bug,
 /// 12 = Rock,   13 = Ghost,   14 = Dragon,  15 = Dark,
 /// 16 = Steel,  17 = Fairy
-
 const NUM_TYPES: usize = 18;
 
+// Type indices referenced by the historical chart patches below.
+const FIRE: usize = 1;
+const ICE: usize = 5;
+const POISON: usize = 7;
+const PSYCHIC: usize = 10;
+const BUG: usize = 11;
+const GHOST: usize = 13;
+const DARK: usize = 15;
+const STEEL: usize = 16;
+const FAIRY: usize = 17;
+
 /// Flat 18x18 effectiveness matrix.
 /// MATRIX[atk * 18 + def] = multiplier (0.0, 0.5, 1.0, or 2.0).
 #[rustfmt::skip]
@@ -44,24 +81,84 @@ const MATRIX: [f64; NUM_TYPES * NUM_TYPES] = [
     // Dragon attacking
     1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 2.0, 1.0, 0.5, 0.0,
     // Dark attacking
-    1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.5, 1.0, 1.0, 1.0, 2.0, 1.0, 1.0, 2.0, 1.0, 0.5, 0.5, 0.5,
+    1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.5, 1.0, 1.0, 1.0, 2.0, 1.0, 1.0, 2.0, 1.0, 0.5, 1.0, 0.5,
     // Steel attacking
     1.0, 0.5, 0.5, 0.5, 1.0, 2.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 2.0, 1.0, 1.0, 1.0, 0.5, 2.0,
     // Fairy attacking
     1.0, 0.5, 1.0, 1.0, 1.0, 1.0, 2.0, 0.5, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 2.0, 2.0, 0.5, 1.0,
 ];
 
+/// Generation 2-5 chart: no Fairy type, and Steel still resists Ghost and Dark.
+const GEN2_TO_5_MATRIX: [f64; NUM_TYPES * NUM_TYPES] = build_gen2_to_5_matrix();
+
+/// Generation 1 chart: 15 types (no Dark, Steel or Fairy), plus the
+/// cartridge quirks of Red/Blue/Yellow.
+const GEN1_MATRIX: [f64; NUM_TYPES * NUM_TYPES] = build_gen1_matrix();
+
+/// Reset every matchup involving `t` to neutral, for types that do not
+/// exist yet in an older generation.
+const fn clear_type(mut m: [f64; NUM_TYPES * NUM_TYPES], t: usize) -> [f64; NUM_TYPES * NUM_TYPES] {
+    let mut i = 0;
+    while i < NUM_TYPES {
+        m[t * NUM_TYPES + i] = 1.0;
+        m[i * NUM_TYPES + t] = 1.0;
+        i += 1;
+    }
+    m
+}
+
+const fn build_gen2_to_5_matrix() -> [f64; NUM_TYPES * NUM_TYPES] {
+    let mut m = clear_type(MATRIX, FAIRY);
+    m[GHOST * NUM_TYPES + STEEL] = 0.5;
+    m[DARK * NUM_TYPES + STEEL] = 0.5;
+    m
+}
+
+const fn build_gen1_matrix() -> [f64; NUM_TYPES * NUM_TYPES] {
+    let mut m = clear_type(clear_type(GEN2_TO_5_MATRIX, DARK), STEEL);
+    // Ghost moves "don't affect" Psychic types (intended to be 2x)
+    m[GHOST * NUM_TYPES + PSYCHIC] = 0.0;
+    m[BUG * NUM_TYPES + POISON] = 2.0;
+    m[POISON * NUM_TYPES + BUG] = 2.0;
+    m[ICE * NUM_TYPES + FIRE] = 1.0;
+    m
+}
+
+/// Get the effectiveness matrix used by a given generation.
+/// Generation 1 and 2-5 return the historical charts; anything else
+/// (including 0) returns the current Gen 6+ chart.
+///
+/// Types that do not exist in the requested generation are neutral in
+/// both directions.
+pub fn matrix_for_generation(generation: u8) -> &'static [f64; NUM_TYPES * NUM_TYPES] {
+    match generation {
+        1 => &GEN1_MATRIX,
+        2..=5 => &GEN2_TO_5_MATRIX,
+        _ => &MATRIX,
+    }
+}
+
+/// Whether a type index exists in the given generation.
+/// Gen 1 has 15 types (0-14), Gen 2-5 add Dark and Steel, Gen 6+ adds Fairy.
+#[wasm_bindgen]
+pub fn type_exists_in_generation(type_idx: u8, generation: u8) -> bool {
+    let t = type_idx as usize;
+    match generation {
+        1 => t < DARK,
+        2..=5 => t < FAIRY,
+        _ => t < NUM_TYPES,
+    }
+}
+
 /// Get effectiveness multiplier of attack type vs defend type.
 /// Types are passed as u8 indices (0 = Normal through 17 = Fairy).
 /// Returns 1.0 (neutral) for out-of-range indices.
 #[wasm_bindgen]
 pub fn get_effectiveness(atk_type: u8, def_type: u8) -> f64 {
-    let a = atk_type as usize;
-    let d = def_type as usize;
-    if a >= NUM_TYPES || d >= NUM_TYPES {
-        return 1.0;
+    match (PokemonType::from_index(atk_type), PokemonType::from_index(def_type)) {
+        (Some(atk), Some(def)) => effectiveness(atk, def),
+        _ => 1.0,
     }
-    MATRIX[a * NUM_TYPES + d]
 }
 
 /// Get the combined defensive multiplier of an attack type vs a dual-type defender.
@@ -70,9 +167,31 @@ pub fn get_effectiveness(atk_type: u8, def_type: u8) -> f64 {
 /// Returns the product of individual effectiveness values.
 #[wasm_bindgen]
 pub fn get_defensive_multiplier(atk_type: u8, def_type1: u8, def_type2: i8) -> f64 {
-    let mut mult = get_effectiveness(atk_type, def_type1);
+    get_effectiveness(atk_type, def_type1) * get_effectiveness(atk_type, def_type2 as u8)
+}
+
+/// Same as `get_effectiveness`, but using the chart of `generation` (1-9).
+#[wasm_bindgen]
+pub fn get_effectiveness_for_generation(atk_type: u8, def_type: u8, generation: u8) -> f64 {
+    let a = atk_type as usize;
+    let d = def_type as usize;
+    if a >= NUM_TYPES || d >= NUM_TYPES {
+        return 1.0;
+    }
+    matrix_for_generation(generation)[a * NUM_TYPES + d]
+}
+
+/// Same as `get_defensive_multiplier`, but using the chart of `generation` (1-9).
+#[wasm_bindgen]
+pub fn get_defensive_multiplier_for_generation(
+    atk_type: u8,
+    def_type1: u8,
+    def_type2: i8,
+    generation: u8,
+) -> f64 {
+    let mut mult = get_effectiveness_for_generation(atk_type, def_type1, generation);
     if def_type2 >= 0 {
-        mult *= get_effectiveness(atk_type, def_type2 as u8);
+        mult *= get_effectiveness_for_generation(atk_type, def_type2 as u8, generation);
     }
     mult
 }
@@ -123,4 +242,59 @@ mod tests {
     fn test_out_of_range() {
         assert_eq!(get_effectiveness(99, 0), 1.0); // invalid type defaults to 1.0
     }
+
+    #[test]
+    fn test_gen6_matches_default_chart() {
+        for atk in 0..NUM_TYPES as u8 {
+            for def in 0..NUM_TYPES as u8 {
+                assert_eq!(get_effectiveness_for_generation(atk, def, 9), get_effectiveness(atk, def));
+            }
+        }
+    }
+
+    #[test]
+    fn test_gen2_to_5_steel_resists_ghost_and_dark() {
+        assert_eq!(get_effectiveness_for_generation(13, 16, 4), 0.5); // ghost vs steel
+        assert_eq!(get_effectiveness_for_generation(15, 16, 5), 0.5); // dark vs steel
+        assert_eq!(get_effectiveness_for_generation(13, 16, 6), 1.0);
+        assert_eq!(get_effectiveness_for_generation(15, 16, 6), 1.0);
+    }
+
+    #[test]
+    fn test_gen2_to_5_no_fairy() {
+        assert_eq!(get_effectiveness_for_generation(14, 17, 3), 1.0); // dragon vs "fairy"
+        assert_eq!(get_effectiveness_for_generation(17, 14, 3), 1.0);
+        assert!(!type_exists_in_generation(17, 5));
+        assert!(type_exists_in_generation(16, 2));
+    }
+
+    #[test]
+    fn test_gen1_ghost_psychic_bug() {
+        assert_eq!(get_effectiveness_for_generation(13, 10, 1), 0.0); // ghost vs psychic
+        assert_eq!(get_effectiveness_for_generation(13, 10, 2), 2.0);
+    }
+
+    #[test]
+    fn test_gen1_bug_poison_ice_fire() {
+        assert_eq!(get_effectiveness_for_generation(11, 7, 1), 2.0); // bug vs poison
+        assert_eq!(get_effectiveness_for_generation(7, 11, 1), 2.0); // poison vs bug
+        assert_eq!(get_effectiveness_for_generation(5, 1, 1), 1.0); // ice vs fire
+        assert_eq!(get_effectiveness_for_generation(11, 7, 2), 0.5);
+        assert_eq!(get_effectiveness_for_generation(7, 11, 2), 1.0);
+        assert_eq!(get_effectiveness_for_generation(5, 1, 2), 0.5);
+    }
+
+    #[test]
+    fn test_gen1_has_fifteen_types() {
+        let count = (0..NUM_TYPES as u8).filter(|&t| type_exists_in_generation(t, 1)).count();
+        assert_eq!(count, 15);
+        assert_eq!(get_effectiveness_for_generation(6, 16, 1), 1.0); // no Steel to hit
+    }
+
+    #[test]
+    fn test_gen_defensive_dual_type() {
+        // ghost vs psychic/poison: immune in gen 1, 2.0 * 1.0 from gen 2 on
+        assert_eq!(get_defensive_multiplier_for_generation(13, 10, 7, 1), 0.0);
+        assert_eq!(get_defensive_multiplier_for_generation(13, 10, 7, 6), 2.0);
+    }
 }
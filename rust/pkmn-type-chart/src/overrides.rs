@@ -0,0 +1,394 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{MATRIX, NUM_TYPES};
+
+const NORMAL: u8 = 0;
+const FIRE: u8 = 1;
+const WATER: u8 = 2;
+const ELECTRIC: u8 = 3;
+const GRASS: u8 = 4;
+const FIGHTING: u8 = 6;
+const GROUND: u8 = 8;
+const FLYING: u8 = 9;
+const GHOST: u8 = 13;
+
+// ---------------------------------------------------------------------------
+// Move and field flags (combine with `|`)
+// ---------------------------------------------------------------------------
+
+/// Freeze-Dry: super effective against Water regardless of the chart.
+pub const MOVE_FREEZE_DRY: u32 = 1 << 0;
+/// Thousand Arrows: hits ungrounded targets, neutral against airborne Flying types.
+pub const MOVE_THOUSAND_ARROWS: u32 = 1 << 1;
+
+/// Gravity is active: every Pokemon is grounded.
+pub const FIELD_GRAVITY: u32 = 1 << 0;
+/// The defender is grounded by Smack Down, Ingrain or a previous Thousand Arrows hit.
+pub const FIELD_DEFENDER_GROUNDED: u32 = 1 << 1;
+/// Magic Room is active: held items have no effect.
+pub const FIELD_MAGIC_ROOM: u32 = 1 << 2;
+
+/// Why an attack ends up dealing no damage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ImmunityReason {
+    /// The attack is not blocked.
+    None = 0,
+    /// A defending type is immune on the chart (e.g. Normal vs Ghost).
+    Type = 1,
+    Levitate = 2,
+    AirBalloon = 3,
+    /// Flash Fire or Well-Baked Body.
+    FlashFire = 4,
+    /// Water Absorb, Storm Drain or Dry Skin.
+    WaterAbsorb = 5,
+    /// Volt Absorb, Lightning Rod or Motor Drive.
+    VoltAbsorb = 6,
+    SapSipper = 7,
+    EarthEater = 8,
+    WonderGuard = 9,
+}
+
+/// Final multiplier of an attack plus the reason it was blocked, if it was.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EffectivenessResult {
+    pub multiplier: f64,
+    pub reason: ImmunityReason,
+}
+
+/// Abilities, items and flags that can change type effectiveness.
+///
+/// Ability and item names are matched case-insensitively, ignoring spaces
+/// and hyphens, so `"Water Absorb"`, `"water-absorb"` and `"waterabsorb"`
+/// are equivalent. Empty strings mean "none".
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EffectivenessContext<'a> {
+    pub attacker_ability: &'a str,
+    pub defender_ability: &'a str,
+    pub defender_item: &'a str,
+    pub move_flags: u32,
+    pub field_flags: u32,
+}
+
+/// Normalize an ability, item or move name: lowercase alphanumerics only,
+/// so "Choice Scarf", "choice-scarf" and "choicescarf" compare equal.
+pub fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn chart_effectiveness(matrix: &[f64; NUM_TYPES * NUM_TYPES], atk: u8, def: u8) -> f64 {
+    let a = atk as usize;
+    let d = def as usize;
+    if a >= NUM_TYPES || d >= NUM_TYPES {
+        return 1.0;
+    }
+    matrix[a * NUM_TYPES + d]
+}
+
+/// Context-aware effectiveness against an arbitrary chart.
+/// `def_type2` is -1 for single-type Pokemon.
+pub(crate) fn effectiveness_in_matrix(
+    matrix: &[f64; NUM_TYPES * NUM_TYPES],
+    atk_type: u8,
+    def_type1: u8,
+    def_type2: i8,
+    ctx: &EffectivenessContext,
+) -> EffectivenessResult {
+    let attacker_ability = normalize_name(ctx.attacker_ability);
+    let ignores_ability = matches!(
+        attacker_ability.as_str(),
+        "moldbreaker" | "teravolt" | "turboblaze"
+    );
+    let defender_ability = if ignores_ability {
+        String::new()
+    } else {
+        normalize_name(ctx.defender_ability)
+    };
+    let defender_item = if ctx.field_flags & FIELD_MAGIC_ROOM != 0 {
+        String::new()
+    } else {
+        normalize_name(ctx.defender_item)
+    };
+
+    let scrappy = matches!(attacker_ability.as_str(), "scrappy" | "mindseye");
+    let ring_target = defender_item == "ringtarget";
+    let thousand_arrows = ctx.move_flags & MOVE_THOUSAND_ARROWS != 0;
+    let grounded = ctx.field_flags & (FIELD_GRAVITY | FIELD_DEFENDER_GROUNDED) != 0
+        || defender_item == "ironball";
+
+    let mut def_types = vec![def_type1];
+    if def_type2 >= 0 {
+        def_types.push(def_type2 as u8);
+    }
+
+    // Thousand Arrows deals neutral damage to an airborne Flying type
+    if atk_type == GROUND && thousand_arrows && !grounded && def_types.contains(&FLYING) {
+        return EffectivenessResult {
+            multiplier: 1.0,
+            reason: ImmunityReason::None,
+        };
+    }
+
+    let mut multiplier = 1.0;
+    for &def in &def_types {
+        let mut eff = chart_effectiveness(matrix, atk_type, def);
+        if ctx.move_flags & MOVE_FREEZE_DRY != 0 && def == WATER {
+            eff = 2.0;
+        }
+        if eff == 0.0 {
+            let lifted = ring_target
+                || (scrappy && (atk_type == NORMAL || atk_type == FIGHTING) && def == GHOST)
+                || (atk_type == GROUND && def == FLYING && grounded);
+            if lifted {
+                eff = 1.0;
+            }
+        }
+        multiplier *= eff;
+    }
+
+    if multiplier == 0.0 {
+        return EffectivenessResult {
+            multiplier,
+            reason: ImmunityReason::Type,
+        };
+    }
+
+    let blocked = |reason| EffectivenessResult {
+        multiplier: 0.0,
+        reason,
+    };
+
+    if atk_type == GROUND && !grounded && !thousand_arrows {
+        if defender_ability == "levitate" {
+            return blocked(ImmunityReason::Levitate);
+        }
+        if defender_item == "airballoon" {
+            return blocked(ImmunityReason::AirBalloon);
+        }
+    }
+
+    match (atk_type, defender_ability.as_str()) {
+        (FIRE, "flashfire" | "wellbakedbody") => return blocked(ImmunityReason::FlashFire),
+        (WATER, "waterabsorb" | "stormdrain" | "dryskin") => {
+            return blocked(ImmunityReason::WaterAbsorb)
+        }
+        (ELECTRIC, "voltabsorb" | "lightningrod" | "motordrive") => {
+            return blocked(ImmunityReason::VoltAbsorb)
+        }
+        (GRASS, "sapsipper") => return blocked(ImmunityReason::SapSipper),
+        (GROUND, "eartheater") => return blocked(ImmunityReason::EarthEater),
+        _ => {}
+    }
+
+    if defender_ability == "wonderguard" && multiplier <= 1.0 {
+        return blocked(ImmunityReason::WonderGuard);
+    }
+
+    EffectivenessResult {
+        multiplier,
+        reason: ImmunityReason::None,
+    }
+}
+
+/// Effectiveness of an attack once abilities, items, move quirks and field
+/// conditions are taken into account. `def_type2` is -1 for single-type Pokemon.
+pub fn effectiveness_with_context(
+    atk_type: u8,
+    def_type1: u8,
+    def_type2: i8,
+    ctx: &EffectivenessContext,
+) -> EffectivenessResult {
+    effectiveness_in_matrix(&MATRIX, atk_type, def_type1, def_type2, ctx)
+}
+
+/// Effectiveness with ability, item, move and field overrides applied.
+///
+/// Parameters:
+/// - `atk_type`, `def_type1`: type indices (0-17)
+/// - `def_type2`: secondary type index, or -1 for single-type Pokemon
+/// - `attacker_ability`, `defender_ability`, `defender_item`: names ("" for none)
+/// - `move_flags`: `MOVE_*` bits (1 = Freeze-Dry, 2 = Thousand Arrows)
+/// - `field_flags`: `FIELD_*` bits (1 = Gravity, 2 = defender grounded, 4 = Magic Room)
+///
+/// Returns a `Vec<f64>` of 2 values: `[multiplier, reason]`, where `reason`
+/// is an `ImmunityReason` code (0 when the attack is not blocked).
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn get_effectiveness_with_context(
+    atk_type: u8,
+    def_type1: u8,
+    def_type2: i8,
+    attacker_ability: &str,
+    defender_ability: &str,
+    defender_item: &str,
+    move_flags: u32,
+    field_flags: u32,
+) -> Vec<f64> {
+    let ctx = EffectivenessContext {
+        attacker_ability,
+        defender_ability,
+        defender_item,
+        move_flags,
+        field_flags,
+    };
+    let result = effectiveness_with_context(atk_type, def_type1, def_type2, &ctx);
+    vec![result.multiplier, result.reason as u8 as f64]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ICE: u8 = 5;
+    const STEEL: u8 = 16;
+
+    fn query(atk: u8, def1: u8, def2: i8, ctx: EffectivenessContext) -> EffectivenessResult {
+        effectiveness_with_context(atk, def1, def2, &ctx)
+    }
+
+    fn defender(ability: &str) -> EffectivenessContext<'_> {
+        EffectivenessContext {
+            defender_ability: ability,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_plain_chart_without_context() {
+        let r = query(FIRE, GRASS, -1, EffectivenessContext::default());
+        assert_eq!(r.multiplier, 2.0);
+        assert_eq!(r.reason, ImmunityReason::None);
+
+        let r = query(NORMAL, GHOST, -1, EffectivenessContext::default());
+        assert_eq!(r.multiplier, 0.0);
+        assert_eq!(r.reason, ImmunityReason::Type);
+    }
+
+    #[test]
+    fn test_levitate_and_air_balloon() {
+        let r = query(GROUND, STEEL, -1, defender("Levitate"));
+        assert_eq!(r.multiplier, 0.0);
+        assert_eq!(r.reason, ImmunityReason::Levitate);
+
+        let ctx = EffectivenessContext {
+            defender_item: "air-balloon",
+            ..Default::default()
+        };
+        assert_eq!(query(GROUND, FIRE, -1, ctx).reason, ImmunityReason::AirBalloon);
+    }
+
+    #[test]
+    fn test_absorbing_abilities() {
+        assert_eq!(query(FIRE, GRASS, -1, defender("flash-fire")).reason, ImmunityReason::FlashFire);
+        assert_eq!(query(WATER, FIRE, -1, defender("Storm Drain")).reason, ImmunityReason::WaterAbsorb);
+        assert_eq!(query(ELECTRIC, WATER, -1, defender("motor-drive")).reason, ImmunityReason::VoltAbsorb);
+        assert_eq!(query(GRASS, WATER, -1, defender("sap-sipper")).reason, ImmunityReason::SapSipper);
+        assert_eq!(query(GROUND, ELECTRIC, -1, defender("earth-eater")).reason, ImmunityReason::EarthEater);
+    }
+
+    #[test]
+    fn test_wonder_guard() {
+        // Shedinja: Bug/Ghost
+        let r = query(NORMAL, 11, GHOST as i8, defender("wonder-guard"));
+        assert_eq!(r.reason, ImmunityReason::Type);
+        let r = query(WATER, 11, GHOST as i8, defender("wonder-guard"));
+        assert_eq!(r.multiplier, 0.0);
+        assert_eq!(r.reason, ImmunityReason::WonderGuard);
+        let r = query(FIRE, 11, GHOST as i8, defender("wonder-guard"));
+        assert_eq!(r.multiplier, 2.0);
+    }
+
+    #[test]
+    fn test_mold_breaker_ignores_defender_ability() {
+        let ctx = EffectivenessContext {
+            attacker_ability: "mold-breaker",
+            defender_ability: "levitate",
+            ..Default::default()
+        };
+        assert_eq!(query(GROUND, STEEL, -1, ctx).multiplier, 2.0);
+    }
+
+    #[test]
+    fn test_scrappy_hits_ghost() {
+        let ctx = EffectivenessContext {
+            attacker_ability: "scrappy",
+            ..Default::default()
+        };
+        assert_eq!(query(NORMAL, GHOST, -1, ctx).multiplier, 1.0);
+        // Fighting vs Ghost/Dark: 1.0 * 2.0
+        assert_eq!(query(FIGHTING, GHOST, 15, ctx).multiplier, 2.0);
+    }
+
+    #[test]
+    fn test_freeze_dry() {
+        let ctx = EffectivenessContext {
+            move_flags: MOVE_FREEZE_DRY,
+            ..Default::default()
+        };
+        assert_eq!(query(ICE, WATER, -1, ctx).multiplier, 2.0);
+        // Water/Ground: 2.0 * 2.0
+        assert_eq!(query(ICE, WATER, GROUND as i8, ctx).multiplier, 4.0);
+    }
+
+    #[test]
+    fn test_thousand_arrows() {
+        let ctx = EffectivenessContext {
+            move_flags: MOVE_THOUSAND_ARROWS,
+            ..Default::default()
+        };
+        // Fire/Flying takes neutral damage rather than 2x
+        assert_eq!(query(GROUND, FIRE, FLYING as i8, ctx).multiplier, 1.0);
+        let ctx = EffectivenessContext {
+            move_flags: MOVE_THOUSAND_ARROWS,
+            defender_ability: "levitate",
+            ..Default::default()
+        };
+        assert_eq!(query(GROUND, FIRE, -1, ctx).multiplier, 2.0);
+    }
+
+    #[test]
+    fn test_grounding_effects() {
+        let gravity = EffectivenessContext {
+            field_flags: FIELD_GRAVITY,
+            defender_ability: "levitate",
+            ..Default::default()
+        };
+        assert_eq!(query(GROUND, STEEL, -1, gravity).multiplier, 2.0);
+        // Grounded Fire/Flying: 2.0 * 1.0
+        assert_eq!(query(GROUND, FIRE, FLYING as i8, gravity).multiplier, 2.0);
+
+        let iron_ball = EffectivenessContext {
+            defender_item: "Iron Ball",
+            ..Default::default()
+        };
+        assert_eq!(query(GROUND, FLYING, -1, iron_ball).multiplier, 1.0);
+
+        let magic_room = EffectivenessContext {
+            defender_item: "iron-ball",
+            field_flags: FIELD_MAGIC_ROOM,
+            ..Default::default()
+        };
+        assert_eq!(query(GROUND, FLYING, -1, magic_room).reason, ImmunityReason::Type);
+    }
+
+    #[test]
+    fn test_ring_target() {
+        let ctx = EffectivenessContext {
+            defender_item: "ring-target",
+            ..Default::default()
+        };
+        assert_eq!(query(NORMAL, GHOST, -1, ctx).multiplier, 1.0);
+        assert_eq!(query(ELECTRIC, GROUND, -1, ctx).multiplier, 1.0);
+        // Only immunities are removed: Fighting vs Ghost/Steel = 1.0 * 2.0
+        assert_eq!(query(FIGHTING, GHOST, STEEL as i8, ctx).multiplier, 2.0);
+    }
+
+    #[test]
+    fn test_wasm_wrapper_layout() {
+        let r = get_effectiveness_with_context(WATER, FIRE, -1, "", "water-absorb", "", 0, 0);
+        assert_eq!(r, vec![0.0, ImmunityReason::WaterAbsorb as u8 as f64]);
+    }
+}
@@ -0,0 +1,262 @@
+use wasm_bindgen::prelude::*;
+
+use crate::chart::TypeChart;
+use crate::types::PokemonType;
+
+/// Number of distinct typings: 18 mono-types plus 153 dual-types.
+pub const NUM_TYPE_COMBINATIONS: usize = 171;
+
+/// Every mono and dual typing, ordered as `(t1, None)` followed by
+/// `(t1, Some(t2))` for each `t2 > t1`, for `t1` from Normal to Fairy.
+pub fn all_type_combinations() -> Vec<(PokemonType, Option<PokemonType>)> {
+    let mut combos = Vec::with_capacity(NUM_TYPE_COMBINATIONS);
+    for (i, &t1) in PokemonType::ALL.iter().enumerate() {
+        combos.push((t1, None));
+        for &t2 in &PokemonType::ALL[i + 1..] {
+            combos.push((t1, Some(t2)));
+        }
+    }
+    combos
+}
+
+/// Attacking types grouped by how much damage they deal to one typing.
+///
+/// Multipliers are bucketed by range so that custom charts with unusual
+/// values still land somewhere: `>= 4`, `> 1`, `== 1`, `> 0.25`, `> 0`, `0`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DefensiveProfile {
+    pub quadruple: Vec<PokemonType>,
+    pub double: Vec<PokemonType>,
+    pub neutral: Vec<PokemonType>,
+    pub half: Vec<PokemonType>,
+    pub quarter: Vec<PokemonType>,
+    pub immune: Vec<PokemonType>,
+}
+
+impl DefensiveProfile {
+    /// Buckets in display order: 4x, 2x, 1x, 0.5x, 0.25x, 0x.
+    pub fn buckets(&self) -> [&[PokemonType]; 6] {
+        [
+            &self.quadruple,
+            &self.double,
+            &self.neutral,
+            &self.half,
+            &self.quarter,
+            &self.immune,
+        ]
+    }
+}
+
+/// Best hit available against one typing from a set of attacking types.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OffensiveEntry {
+    pub def_type1: PokemonType,
+    pub def_type2: Option<PokemonType>,
+    pub best_multiplier: f64,
+    /// `None` when no attacking types were given.
+    pub best_type: Option<PokemonType>,
+}
+
+/// Coverage of a set of attacking types against all 171 typings.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OffensiveProfile {
+    pub entries: Vec<OffensiveEntry>,
+    /// Typings hit for more than 1x by at least one attacking type.
+    pub super_effective: usize,
+    pub neutral: usize,
+    /// Typings where the best hit is resisted (but not immune).
+    pub resisted: usize,
+    pub immune: usize,
+}
+
+impl TypeChart {
+    /// Defensive profile of a mono or dual typing.
+    pub fn defensive_profile(
+        &self,
+        def_type1: PokemonType,
+        def_type2: Option<PokemonType>,
+    ) -> DefensiveProfile {
+        let mut profile = DefensiveProfile::default();
+        for atk in PokemonType::ALL {
+            let mult = self.matchup(atk, def_type1, def_type2);
+            let bucket = if mult == 0.0 {
+                &mut profile.immune
+            } else if mult <= 0.25 {
+                &mut profile.quarter
+            } else if mult < 1.0 {
+                &mut profile.half
+            } else if mult == 1.0 {
+                &mut profile.neutral
+            } else if mult < 4.0 {
+                &mut profile.double
+            } else {
+                &mut profile.quadruple
+            };
+            bucket.push(atk);
+        }
+        profile
+    }
+
+    /// Offensive profile of a set of attacking types (e.g. a moveset or a
+    /// team's STABs) against every typing. Duplicates in `atk_types` are harmless.
+    pub fn offensive_profile(&self, atk_types: &[PokemonType]) -> OffensiveProfile {
+        let mut profile = OffensiveProfile::default();
+        for (def_type1, def_type2) in all_type_combinations() {
+            let mut best_multiplier = if atk_types.is_empty() { 1.0 } else { f64::MIN };
+            let mut best_type = None;
+            for &atk in atk_types {
+                let mult = self.matchup(atk, def_type1, def_type2);
+                if mult > best_multiplier {
+                    best_multiplier = mult;
+                    best_type = Some(atk);
+                }
+            }
+
+            if best_multiplier > 1.0 {
+                profile.super_effective += 1;
+            } else if best_multiplier == 1.0 {
+                profile.neutral += 1;
+            } else if best_multiplier > 0.0 {
+                profile.resisted += 1;
+            } else {
+                profile.immune += 1;
+            }
+
+            profile.entries.push(OffensiveEntry {
+                def_type1,
+                def_type2,
+                best_multiplier,
+                best_type,
+            });
+        }
+        profile
+    }
+}
+
+/// Defensive profile of a typing, bucketed by multiplier.
+///
+/// `def_type2` is -1 for single-type Pokemon. Invalid `def_type1` returns an empty vector.
+///
+/// Returns a flat `Vec<f64>` of six groups in the order 4x, 2x, 1x, 0.5x, 0.25x, 0x.
+/// Each group is `[count, type_idx...]`.
+#[wasm_bindgen]
+pub fn get_defensive_profile(def_type1: u8, def_type2: i8) -> Vec<f64> {
+    let Some(def1) = PokemonType::from_index(def_type1) else {
+        return Vec::new();
+    };
+    let def2 = PokemonType::from_index(def_type2 as u8);
+    let profile = TypeChart::default().defensive_profile(def1, def2);
+
+    let mut result = Vec::with_capacity(6 + 18);
+    for bucket in profile.buckets() {
+        result.push(bucket.len() as f64);
+        result.extend(bucket.iter().map(|t| t.index() as f64));
+    }
+    result
+}
+
+/// Offensive profile of a set of attacking type indices against all 171 typings.
+/// Indices outside 0-17 are ignored.
+///
+/// Returns a flat `Vec<f64>`:
+/// [0..4]: counts of (super effective, neutral, resisted, immune) typings
+/// Then 171 entries of 4 values each, in `all_type_combinations` order:
+///   [def_type1, def_type2 (-1 for mono), best_multiplier, best_atk_type (-1 if none)]
+#[wasm_bindgen]
+pub fn get_offensive_profile(atk_types: &[u8]) -> Vec<f64> {
+    let atk: Vec<PokemonType> = atk_types
+        .iter()
+        .filter_map(|&t| PokemonType::from_index(t))
+        .collect();
+    let profile = TypeChart::default().offensive_profile(&atk);
+
+    let mut result = Vec::with_capacity(4 + NUM_TYPE_COMBINATIONS * 4);
+    result.push(profile.super_effective as f64);
+    result.push(profile.neutral as f64);
+    result.push(profile.resisted as f64);
+    result.push(profile.immune as f64);
+    for entry in &profile.entries {
+        result.push(entry.def_type1.index() as f64);
+        result.push(entry.def_type2.map_or(-1.0, |t| t.index() as f64));
+        result.push(entry.best_multiplier);
+        result.push(entry.best_type.map_or(-1.0, |t| t.index() as f64));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use PokemonType::*;
+
+    #[test]
+    fn test_combination_count() {
+        let combos = all_type_combinations();
+        assert_eq!(combos.len(), NUM_TYPE_COMBINATIONS);
+        assert_eq!(combos[0], (Normal, None));
+        assert_eq!(combos[1], (Normal, Some(Fire)));
+        assert_eq!(combos.last(), Some(&(Fairy, None)));
+        assert_eq!(combos.iter().filter(|(_, t2)| t2.is_none()).count(), 18);
+    }
+
+    #[test]
+    fn test_defensive_profile_dual_type() {
+        // Water/Ground: 4x Grass, immune to Electric
+        let profile = TypeChart::default().defensive_profile(Water, Some(Ground));
+        assert_eq!(profile.quadruple, vec![Grass]);
+        assert!(profile.double.is_empty());
+        assert_eq!(profile.immune, vec![Electric]);
+        assert_eq!(profile.half, vec![Fire, Poison, Rock, Steel]);
+        let total: usize = profile.buckets().iter().map(|b| b.len()).sum();
+        assert_eq!(total, 18);
+    }
+
+    #[test]
+    fn test_defensive_profile_quarter() {
+        // Steel/Fairy: Bug is resisted by both types
+        let profile = TypeChart::default().defensive_profile(Steel, Some(Fairy));
+        assert!(profile.quarter.contains(&Bug));
+        assert!(profile.immune.contains(&Poison));
+        assert!(profile.immune.contains(&Dragon));
+    }
+
+    #[test]
+    fn test_offensive_profile() {
+        let profile = TypeChart::default().offensive_profile(&[Ground, Flying]);
+        assert_eq!(profile.entries.len(), NUM_TYPE_COMBINATIONS);
+        assert_eq!(
+            profile.super_effective + profile.neutral + profile.resisted + profile.immune,
+            NUM_TYPE_COMBINATIONS
+        );
+        // Flying hits every typing that is immune to Ground
+        assert_eq!(profile.immune, 0);
+        let fire_steel = profile
+            .entries
+            .iter()
+            .find(|e| e.def_type1 == Fire && e.def_type2 == Some(Steel))
+            .unwrap();
+        assert_eq!(fire_steel.best_multiplier, 4.0);
+        assert_eq!(fire_steel.best_type, Some(Ground));
+    }
+
+    #[test]
+    fn test_offensive_profile_empty() {
+        let profile = TypeChart::default().offensive_profile(&[]);
+        assert_eq!(profile.neutral, NUM_TYPE_COMBINATIONS);
+        assert!(profile.entries.iter().all(|e| e.best_type.is_none()));
+    }
+
+    #[test]
+    fn test_flat_layouts() {
+        let flat = get_defensive_profile(Water.index(), Ground.index() as i8);
+        // 4x group: [1, Grass]
+        assert_eq!(&flat[..2], &[1.0, Grass.index() as f64]);
+        assert_eq!(flat.len(), 6 + 18);
+        assert!(get_defensive_profile(99, -1).is_empty());
+
+        let flat = get_offensive_profile(&[Normal.index(), 255]);
+        assert_eq!(flat.len(), 4 + NUM_TYPE_COMBINATIONS * 4);
+        // Normal vs mono-Normal (first entry)
+        assert_eq!(&flat[4..8], &[0.0, -1.0, 1.0, 0.0]);
+    }
+}
@@ -0,0 +1,333 @@
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::overrides::{effectiveness_with_context, EffectivenessContext, ImmunityReason};
+use crate::rng::Xorshift32;
+use crate::types::{effectiveness, PokemonType};
+
+fn pick_type(rng: &mut Xorshift32) -> PokemonType {
+    PokemonType::ALL[rng.pick(PokemonType::ALL.len())]
+}
+
+// ---------------------------------------------------------------------------
+// Quiz types
+// ---------------------------------------------------------------------------
+
+/// How hard the generated questions are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuizDifficulty {
+    /// Single-type defenders, four fixed answers (0x, 0.5x, 1x, 2x).
+    Easy,
+    /// Mono or dual-type defenders.
+    Normal,
+    /// Dual-type defenders whose answer is a 4x, 0.25x or 0x trap.
+    Hard,
+    /// Hard questions where the defender may also have an ability that changes the answer.
+    Expert,
+}
+
+impl QuizDifficulty {
+    /// 0 = Easy, 1 = Normal, 2 = Hard, 3+ = Expert.
+    pub fn from_u8(difficulty: u8) -> Self {
+        match difficulty {
+            0 => QuizDifficulty::Easy,
+            1 => QuizDifficulty::Normal,
+            2 => QuizDifficulty::Hard,
+            _ => QuizDifficulty::Expert,
+        }
+    }
+}
+
+/// A single multiple-choice matchup question.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuizQuestion {
+    pub attack_type: PokemonType,
+    /// One or two defending types.
+    pub defender_types: Vec<PokemonType>,
+    /// Display name of the defender's ability, if the question involves one.
+    pub defender_ability: Option<String>,
+    /// Candidate multipliers, in ascending order.
+    pub choices: Vec<f64>,
+    /// Index into `choices` of the right answer.
+    pub correct_index: usize,
+    pub multiplier: f64,
+    pub explanation: String,
+}
+
+/// Multipliers offered as answers on Normal difficulty and above.
+const ANSWER_POOL: [f64; 6] = [0.0, 0.25, 0.5, 1.0, 2.0, 4.0];
+const EASY_CHOICES: [f64; 4] = [0.0, 0.5, 1.0, 2.0];
+
+/// Abilities that can appear on Expert questions, with the attacking type they block
+/// (`None` for Wonder Guard, which blocks anything not super effective).
+const QUIZ_ABILITIES: [(&str, Option<PokemonType>); 7] = [
+    ("Levitate", Some(PokemonType::Ground)),
+    ("Earth Eater", Some(PokemonType::Ground)),
+    ("Flash Fire", Some(PokemonType::Fire)),
+    ("Water Absorb", Some(PokemonType::Water)),
+    ("Volt Absorb", Some(PokemonType::Electric)),
+    ("Sap Sipper", Some(PokemonType::Grass)),
+    ("Wonder Guard", None),
+];
+
+/// Maximum attempts at rolling a trap typing before settling for any dual type.
+const MAX_TRAP_ROLLS: usize = 64;
+
+fn format_multiplier(mult: f64) -> String {
+    format!("{}x", mult)
+}
+
+fn describe(mult: f64) -> &'static str {
+    if mult == 0.0 {
+        "has no effect on"
+    } else if mult > 1.0 {
+        "is super effective against"
+    } else if mult < 1.0 {
+        "is not very effective against"
+    } else {
+        "is neutral against"
+    }
+}
+
+fn is_trap(mult: f64) -> bool {
+    mult == 0.0 || mult == 0.25 || mult == 4.0
+}
+
+fn roll_dual(rng: &mut Xorshift32) -> (PokemonType, PokemonType) {
+    let t1 = pick_type(rng);
+    let mut t2 = pick_type(rng);
+    while t2 == t1 {
+        t2 = pick_type(rng);
+    }
+    (t1, t2)
+}
+
+fn build_explanation(
+    attack: PokemonType,
+    defenders: &[PokemonType],
+    type_mult: f64,
+    ability: Option<&str>,
+    reason: ImmunityReason,
+) -> String {
+    let parts: Vec<String> = defenders
+        .iter()
+        .map(|&d| {
+            let m = effectiveness(attack, d);
+            format!("{} {} {} ({})", attack, describe(m), d, format_multiplier(m))
+        })
+        .collect();
+    let mut text = parts.join(", and ");
+    if defenders.len() > 1 {
+        text.push_str(&format!(", for {} overall", format_multiplier(type_mult)));
+    }
+    text.push('.');
+
+    if let Some(ability) = ability {
+        match reason {
+            ImmunityReason::None | ImmunityReason::Type => {
+                text.push_str(&format!(" {} does not change this.", ability));
+            }
+            ImmunityReason::WonderGuard => {
+                text.push_str(" Wonder Guard blocks every hit that is not super effective.");
+            }
+            _ => {
+                text.push_str(&format!(" However, {} makes it immune to {} moves.", ability, attack));
+            }
+        }
+    }
+    text
+}
+
+fn choices_for(rng: &mut Xorshift32, difficulty: QuizDifficulty, answer: f64) -> (Vec<f64>, usize) {
+    let mut choices: Vec<f64> = if difficulty == QuizDifficulty::Easy {
+        EASY_CHOICES.to_vec()
+    } else {
+        let mut pool: Vec<f64> = ANSWER_POOL.iter().copied().filter(|&m| m != answer).collect();
+        let mut picked = vec![answer];
+        while picked.len() < 4 {
+            let i = rng.pick(pool.len());
+            picked.push(pool.swap_remove(i));
+        }
+        picked
+    };
+    choices.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let correct = choices.iter().position(|&m| m == answer).unwrap_or(0);
+    (choices, correct)
+}
+
+fn generate_question(rng: &mut Xorshift32, difficulty: QuizDifficulty) -> QuizQuestion {
+    let attack = pick_type(rng);
+
+    let defenders: Vec<PokemonType> = match difficulty {
+        QuizDifficulty::Easy => vec![pick_type(rng)],
+        QuizDifficulty::Normal => {
+            if rng.next_f64() < 0.5 {
+                vec![pick_type(rng)]
+            } else {
+                let (t1, t2) = roll_dual(rng);
+                vec![t1, t2]
+            }
+        }
+        QuizDifficulty::Hard | QuizDifficulty::Expert => {
+            let mut pair = roll_dual(rng);
+            for _ in 0..MAX_TRAP_ROLLS {
+                let mult = effectiveness(attack, pair.0) * effectiveness(attack, pair.1);
+                if is_trap(mult) {
+                    break;
+                }
+                pair = roll_dual(rng);
+            }
+            vec![pair.0, pair.1]
+        }
+    };
+    let type_mult: f64 = defenders.iter().map(|&d| effectiveness(attack, d)).product();
+
+    // Expert: 60% of questions add an ability, biased towards one that matters
+    let ability = if difficulty == QuizDifficulty::Expert && rng.next_f64() < 0.6 {
+        let relevant: Vec<&str> = QUIZ_ABILITIES
+            .iter()
+            .filter(|(_, blocks)| *blocks == Some(attack))
+            .map(|(name, _)| *name)
+            .collect();
+        if !relevant.is_empty() && rng.next_f64() < 0.5 {
+            Some(relevant[rng.pick(relevant.len())])
+        } else {
+            Some(QUIZ_ABILITIES[rng.pick(QUIZ_ABILITIES.len())].0)
+        }
+    } else {
+        None
+    };
+
+    let (multiplier, reason) = match ability {
+        Some(name) => {
+            let ctx = EffectivenessContext {
+                defender_ability: name,
+                ..Default::default()
+            };
+            let def2 = defenders.get(1).map_or(-1, |t| t.index() as i8);
+            let r = effectiveness_with_context(attack.index(), defenders[0].index(), def2, &ctx);
+            (r.multiplier, r.reason)
+        }
+        None => (type_mult, ImmunityReason::None),
+    };
+
+    let (choices, correct_index) = choices_for(rng, difficulty, multiplier);
+    let explanation = build_explanation(attack, &defenders, type_mult, ability, reason);
+
+    QuizQuestion {
+        attack_type: attack,
+        defender_types: defenders,
+        defender_ability: ability.map(str::to_string),
+        choices,
+        correct_index,
+        multiplier,
+        explanation,
+    }
+}
+
+/// Generate `count` questions. The same seed and difficulty always produce
+/// the same quiz, so a quiz can be shared as just `(seed, difficulty)`.
+pub fn generate_quiz(seed: u32, difficulty: QuizDifficulty, count: usize) -> Vec<QuizQuestion> {
+    let mut rng = Xorshift32::new(seed);
+    (0..count).map(|_| generate_question(&mut rng, difficulty)).collect()
+}
+
+/// Generate a seeded type-matchup quiz.
+///
+/// Parameters:
+/// - `seed`: random seed; the same seed gives the same questions
+/// - `difficulty`: 0 = easy, 1 = normal, 2 = hard (4x/0x traps), 3 = expert (abilities)
+/// - `count`: number of questions
+///
+/// Returns an array of `{ attackType, defenderTypes, defenderAbility, choices,
+/// correctIndex, multiplier, explanation }` objects, with lowercase type names.
+#[wasm_bindgen]
+pub fn generate_type_quiz(seed: u32, difficulty: u8, count: u8) -> JsValue {
+    let quiz = generate_quiz(seed, QuizDifficulty::from_u8(difficulty), count as usize);
+    serde_wasm_bindgen::to_value(&quiz).unwrap_or(JsValue::NULL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic_for_seed() {
+        for difficulty in 0..4 {
+            let d = QuizDifficulty::from_u8(difficulty);
+            assert_eq!(generate_quiz(42, d, 20), generate_quiz(42, d, 20));
+        }
+        assert_ne!(
+            generate_quiz(1, QuizDifficulty::Normal, 20),
+            generate_quiz(2, QuizDifficulty::Normal, 20)
+        );
+    }
+
+    #[test]
+    fn test_answer_is_among_choices() {
+        for difficulty in 0..4 {
+            for q in generate_quiz(7, QuizDifficulty::from_u8(difficulty), 50) {
+                assert_eq!(q.choices.len(), 4);
+                assert_eq!(q.choices[q.correct_index], q.multiplier);
+                assert!(q.choices.windows(2).all(|w| w[0] < w[1]), "choices sorted and unique");
+            }
+        }
+    }
+
+    #[test]
+    fn test_easy_is_single_type() {
+        for q in generate_quiz(99, QuizDifficulty::Easy, 50) {
+            assert_eq!(q.defender_types.len(), 1);
+            assert_eq!(q.choices, EASY_CHOICES.to_vec());
+            assert!(q.defender_ability.is_none());
+            assert_eq!(q.multiplier, effectiveness(q.attack_type, q.defender_types[0]));
+        }
+    }
+
+    #[test]
+    fn test_hard_prefers_traps() {
+        let quiz = generate_quiz(1234, QuizDifficulty::Hard, 50);
+        assert!(quiz.iter().all(|q| q.defender_types.len() == 2));
+        let traps = quiz.iter().filter(|q| is_trap(q.multiplier)).count();
+        assert!(traps >= 45, "expected mostly trap questions, got {}", traps);
+    }
+
+    #[test]
+    fn test_expert_abilities_are_applied() {
+        let quiz = generate_quiz(5, QuizDifficulty::Expert, 100);
+        let with_ability: Vec<&QuizQuestion> =
+            quiz.iter().filter(|q| q.defender_ability.is_some()).collect();
+        assert!(!with_ability.is_empty());
+        for q in with_ability {
+            let ctx = EffectivenessContext {
+                defender_ability: q.defender_ability.as_deref().unwrap(),
+                ..Default::default()
+            };
+            let def2 = q.defender_types.get(1).map_or(-1, |t| t.index() as i8);
+            let r = effectiveness_with_context(
+                q.attack_type.index(),
+                q.defender_types[0].index(),
+                def2,
+                &ctx,
+            );
+            assert_eq!(q.multiplier, r.multiplier);
+        }
+    }
+
+    #[test]
+    fn test_explanation_text() {
+        use PokemonType::*;
+        let text = build_explanation(Ground, &[Fire, Steel], 4.0, None, ImmunityReason::None);
+        assert_eq!(
+            text,
+            "Ground is super effective against Fire (2x), and Ground is super effective \
+             against Steel (2x), for 4x overall."
+        );
+        let text = build_explanation(Ground, &[Fire], 2.0, Some("Levitate"), ImmunityReason::Levitate);
+        assert_eq!(
+            text,
+            "Ground is super effective against Fire (2x). However, Levitate makes it immune to Ground moves."
+        );
+    }
+}
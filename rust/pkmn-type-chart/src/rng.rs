@@ -0,0 +1,33 @@
+// ---------------------------------------------------------------------------
+// Deterministic xorshift32 PRNG (same as pkmn-catch-rate)
+// ---------------------------------------------------------------------------
+
+pub(crate) struct Xorshift32 {
+    state: u32,
+}
+
+impl Xorshift32 {
+    pub(crate) fn new(seed: u32) -> Self {
+        Self {
+            state: if seed == 0 { 1 } else { seed },
+        }
+    }
+
+    pub(crate) fn next(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        self.next() as f64 / u32::MAX as f64
+    }
+
+    /// Uniform index in `[0, len)`.
+    pub(crate) fn pick(&mut self, len: usize) -> usize {
+        (self.next() % len as u32) as usize
+    }
+}
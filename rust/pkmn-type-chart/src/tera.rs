@@ -0,0 +1,208 @@
+use wasm_bindgen::prelude::*;
+
+use crate::chart::TypeChart;
+use crate::NUM_TYPES;
+
+/// Index of the Stellar tera type. It is not on the type chart: a Stellar
+/// Pokemon keeps its original types defensively, and Stellar-type attacks
+/// (Tera Blast, Tera Starstorm) are neutral except against Terastallized targets.
+pub const STELLAR: u8 = 18;
+
+/// Defensive typing after Terastallization, as `(type1, type2)` with -1 for
+/// no second type. `tera_type` is -1 when the Pokemon has not Terastallized.
+/// A regular tera type replaces both types; Stellar keeps the original ones.
+pub fn tera_defensive_types(def_type1: u8, def_type2: i8, tera_type: i8) -> (u8, i8) {
+    if tera_type >= 0 && (tera_type as usize) < NUM_TYPES {
+        (tera_type as u8, -1)
+    } else {
+        (def_type1, def_type2)
+    }
+}
+
+impl TypeChart {
+    /// `get_tera_defensive_multiplier`, evaluated against this chart.
+    pub fn tera_defensive_multiplier(
+        &self,
+        atk_type: u8,
+        def_type1: u8,
+        def_type2: i8,
+        def_tera_type: i8,
+        tera_shell_active: bool,
+    ) -> f64 {
+        let mult = if atk_type == STELLAR {
+            if def_tera_type >= 0 {
+                2.0
+            } else {
+                1.0
+            }
+        } else {
+            let (t1, t2) = tera_defensive_types(def_type1, def_type2, def_tera_type);
+            self.defensive_multiplier(atk_type, t1, t2)
+        };
+        if tera_shell_active && mult > 0.0 {
+            0.5
+        } else {
+            mult
+        }
+    }
+}
+
+/// Defensive multiplier for a possibly Terastallized defender.
+///
+/// Parameters:
+/// - `atk_type`: attacking type (0-17, or 18 for a Stellar Tera Blast)
+/// - `def_type1`, `def_type2`: original types (`def_type2` = -1 for mono-type)
+/// - `def_tera_type`: tera type (0-17, 18 = Stellar), or -1 if not Terastallized
+/// - `tera_shell_active`: Tera Shell at full HP (and not ignored by Mold Breaker);
+///   makes every hit 0.5x, even resisted ones, without removing immunities
+#[wasm_bindgen]
+pub fn get_tera_defensive_multiplier(
+    atk_type: u8,
+    def_type1: u8,
+    def_type2: i8,
+    def_tera_type: i8,
+    tera_shell_active: bool,
+) -> f64 {
+    TypeChart::default().tera_defensive_multiplier(
+        atk_type,
+        def_type1,
+        def_type2,
+        def_tera_type,
+        tera_shell_active,
+    )
+}
+
+/// STAB multiplier for an attacker that may be Terastallized.
+///
+/// Parameters:
+/// - `move_type`: the move's type (0-17, or 18 for a Stellar Tera Blast)
+/// - `atk_type1`, `atk_type2`: original types (`atk_type2` = -1 for mono-type)
+/// - `tera_type`: tera type (0-17, 18 = Stellar), or -1 if not Terastallized
+/// - `adaptability`: attacker has Adaptability
+/// - `stellar_boost_available`: a Stellar attacker has not yet used its
+///   one-time boost for `move_type` (always true for Terapagos)
+///
+/// Rules:
+/// - Not Terastallized: 1.5x on original types (2x with Adaptability)
+/// - Tera type matching an original type: 2x (2.25x with Adaptability)
+/// - Tera type only: 1.5x (2x with Adaptability); original types keep 1.5x
+/// - Stellar: 2x on original types and 1.2x on anything else the first time
+///   each type is used, then regular 1.5x STAB on original types
+#[wasm_bindgen]
+pub fn get_tera_stab_multiplier(
+    move_type: u8,
+    atk_type1: u8,
+    atk_type2: i8,
+    tera_type: i8,
+    adaptability: bool,
+    stellar_boost_available: bool,
+) -> f64 {
+    let original = move_type == atk_type1 || (atk_type2 >= 0 && move_type == atk_type2 as u8);
+
+    if tera_type == STELLAR as i8 {
+        return match (stellar_boost_available, original) {
+            (true, true) => 2.0,
+            (true, false) => 1.2,
+            (false, true) => 1.5,
+            (false, false) => 1.0,
+        };
+    }
+
+    if tera_type >= 0 && move_type == tera_type as u8 {
+        return match (original, adaptability) {
+            (true, true) => 2.25,
+            (true, false) => 2.0,
+            (false, true) => 2.0,
+            (false, false) => 1.5,
+        };
+    }
+
+    if original {
+        // Adaptability only boosts the current typing, so not after a different Tera
+        if adaptability && tera_type < 0 {
+            2.0
+        } else {
+            1.5
+        }
+    } else {
+        1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NORMAL: u8 = 0;
+    const FIRE: u8 = 1;
+    const WATER: u8 = 2;
+    const GRASS: u8 = 4;
+    const GROUND: u8 = 8;
+    const FLYING: u8 = 9;
+    const GHOST: u8 = 13;
+    const STEEL: u8 = 16;
+
+    #[test]
+    fn test_tera_replaces_defensive_typing() {
+        // Fire/Flying weak to Water; Tera Grass resists it
+        assert_eq!(get_tera_defensive_multiplier(WATER, FIRE, FLYING as i8, -1, false), 2.0);
+        assert_eq!(get_tera_defensive_multiplier(WATER, FIRE, FLYING as i8, GRASS as i8, false), 0.5);
+        // Tera drops the Flying immunity to Ground
+        assert_eq!(get_tera_defensive_multiplier(GROUND, STEEL, FLYING as i8, -1, false), 0.0);
+        assert_eq!(get_tera_defensive_multiplier(GROUND, STEEL, FLYING as i8, STEEL as i8, false), 2.0);
+    }
+
+    #[test]
+    fn test_stellar_keeps_original_types() {
+        let stellar = STELLAR as i8;
+        assert_eq!(get_tera_defensive_multiplier(WATER, FIRE, FLYING as i8, stellar, false), 2.0);
+        assert_eq!(tera_defensive_types(FIRE, FLYING as i8, stellar), (FIRE, FLYING as i8));
+    }
+
+    #[test]
+    fn test_stellar_attack() {
+        assert_eq!(get_tera_defensive_multiplier(STELLAR, GHOST, -1, -1, false), 1.0);
+        assert_eq!(get_tera_defensive_multiplier(STELLAR, GHOST, -1, NORMAL as i8, false), 2.0);
+        assert_eq!(get_tera_defensive_multiplier(STELLAR, GHOST, -1, STELLAR as i8, false), 2.0);
+    }
+
+    #[test]
+    fn test_tera_shell() {
+        assert_eq!(get_tera_defensive_multiplier(FIRE, GRASS, -1, -1, true), 0.5);
+        assert_eq!(get_tera_defensive_multiplier(GROUND, FIRE, STEEL as i8, -1, true), 0.5);
+        assert_eq!(get_tera_defensive_multiplier(NORMAL, NORMAL, -1, -1, true), 0.5);
+        // Resistances become 0.5x too; immunities are kept
+        assert_eq!(get_tera_defensive_multiplier(STEEL, FIRE, WATER as i8, -1, true), 0.5);
+        assert_eq!(get_tera_defensive_multiplier(NORMAL, GHOST, -1, -1, true), 0.0);
+    }
+
+    #[test]
+    fn test_stab_without_tera() {
+        assert_eq!(get_tera_stab_multiplier(FIRE, FIRE, -1, -1, false, false), 1.5);
+        assert_eq!(get_tera_stab_multiplier(FIRE, FIRE, -1, -1, true, false), 2.0);
+        assert_eq!(get_tera_stab_multiplier(WATER, FIRE, -1, -1, false, false), 1.0);
+    }
+
+    #[test]
+    fn test_stab_with_tera() {
+        // Tera Fire Fire-type: 2x, 2.25x with Adaptability
+        assert_eq!(get_tera_stab_multiplier(FIRE, FIRE, FLYING as i8, FIRE as i8, false, false), 2.0);
+        assert_eq!(get_tera_stab_multiplier(FIRE, FIRE, FLYING as i8, FIRE as i8, true, false), 2.25);
+        // New tera type: 1.5x, 2x with Adaptability
+        assert_eq!(get_tera_stab_multiplier(WATER, FIRE, -1, WATER as i8, false, false), 1.5);
+        assert_eq!(get_tera_stab_multiplier(WATER, FIRE, -1, WATER as i8, true, false), 2.0);
+        // Original type keeps plain STAB
+        assert_eq!(get_tera_stab_multiplier(FIRE, FIRE, -1, WATER as i8, true, false), 1.5);
+    }
+
+    #[test]
+    fn test_stab_stellar() {
+        let stellar = STELLAR as i8;
+        assert_eq!(get_tera_stab_multiplier(FIRE, FIRE, -1, stellar, false, true), 2.0);
+        assert_eq!(get_tera_stab_multiplier(WATER, FIRE, -1, stellar, false, true), 1.2);
+        assert_eq!(get_tera_stab_multiplier(FIRE, FIRE, -1, stellar, false, false), 1.5);
+        assert_eq!(get_tera_stab_multiplier(WATER, FIRE, -1, stellar, false, false), 1.0);
+        // Stellar Tera Blast counts as a non-STAB type
+        assert_eq!(get_tera_stab_multiplier(STELLAR, FIRE, -1, stellar, false, true), 1.2);
+    }
+}
@@ -0,0 +1,218 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::chart::TypeChart;
+
+/// The 18 Pokemon types, with discriminants matching the numeric indices
+/// used by every crate in this workspace.
+#[wasm_bindgen]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum PokemonType {
+    Normal = 0,
+    Fire = 1,
+    Water = 2,
+    Electric = 3,
+    Grass = 4,
+    Ice = 5,
+    Fighting = 6,
+    Poison = 7,
+    Ground = 8,
+    Flying = 9,
+    Psychic = 10,
+    Bug = 11,
+    Rock = 12,
+    Ghost = 13,
+    Dragon = 14,
+    Dark = 15,
+    Steel = 16,
+    Fairy = 17,
+}
+
+const NAMES: [&str; 18] = [
+    "normal", "fire", "water", "electric", "grass", "ice", "fighting", "poison", "ground",
+    "flying", "psychic", "bug", "rock", "ghost", "dragon", "dark", "steel", "fairy",
+];
+
+impl PokemonType {
+    /// All types in index order.
+    pub const ALL: [PokemonType; 18] = [
+        PokemonType::Normal,
+        PokemonType::Fire,
+        PokemonType::Water,
+        PokemonType::Electric,
+        PokemonType::Grass,
+        PokemonType::Ice,
+        PokemonType::Fighting,
+        PokemonType::Poison,
+        PokemonType::Ground,
+        PokemonType::Flying,
+        PokemonType::Psychic,
+        PokemonType::Bug,
+        PokemonType::Rock,
+        PokemonType::Ghost,
+        PokemonType::Dragon,
+        PokemonType::Dark,
+        PokemonType::Steel,
+        PokemonType::Fairy,
+    ];
+
+    /// Convert a numeric index (0-17). Anything else, including the 255 and
+    /// -1 (as `u8`) mono-type sentinels, returns `None`.
+    pub fn from_index(index: u8) -> Option<PokemonType> {
+        Self::ALL.get(index as usize).copied()
+    }
+
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+
+    /// Lowercase name, matching the TS `TypeName` union ("fire", "water", ...).
+    pub fn name(self) -> &'static str {
+        NAMES[self as usize]
+    }
+}
+
+impl fmt::Display for PokemonType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.name();
+        f.write_str(&name[..1].to_ascii_uppercase())?;
+        f.write_str(&name[1..])
+    }
+}
+
+/// Error returned when a string is not one of the 18 type names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseTypeError(pub String);
+
+impl fmt::Display for ParseTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown Pokemon type: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseTypeError {}
+
+impl FromStr for PokemonType {
+    type Err = ParseTypeError;
+
+    /// Parse a type name, ignoring case and surrounding whitespace.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_ascii_lowercase();
+        NAMES
+            .iter()
+            .position(|&n| n == lower)
+            .map(|i| Self::ALL[i])
+            .ok_or_else(|| ParseTypeError(s.to_string()))
+    }
+}
+
+impl TypeChart {
+    /// Multiplier of `atk` against a mono- or dual-type defender.
+    pub fn matchup(&self, atk: PokemonType, def1: PokemonType, def2: Option<PokemonType>) -> f64 {
+        let mut mult = self.effectiveness(atk.index(), def1.index());
+        if let Some(def2) = def2 {
+            mult *= self.effectiveness(atk.index(), def2.index());
+        }
+        mult
+    }
+}
+
+/// Effectiveness of `atk` vs a single defending type on the standard chart.
+pub fn effectiveness(atk: PokemonType, def: PokemonType) -> f64 {
+    crate::MATRIX[atk as usize * crate::NUM_TYPES + def as usize]
+}
+
+/// Combined multiplier of `atk` vs a mono- or dual-type defender on the standard chart.
+pub fn defensive_multiplier(
+    atk: PokemonType,
+    def1: PokemonType,
+    def2: Option<PokemonType>,
+) -> f64 {
+    effectiveness(atk, def1) * def2.map_or(1.0, |d| effectiveness(atk, d))
+}
+
+/// Parse a type name ("Fire", "fire", " FIRE "). Returns `undefined` if unknown.
+#[wasm_bindgen]
+pub fn type_from_name(name: &str) -> Option<PokemonType> {
+    name.parse().ok()
+}
+
+/// Lowercase name of a type ("fire").
+#[wasm_bindgen]
+pub fn type_name(t: PokemonType) -> String {
+    t.name().to_string()
+}
+
+/// `get_effectiveness` with type names instead of indices.
+/// Unknown names are treated as neutral (1.0).
+#[wasm_bindgen]
+pub fn get_effectiveness_by_name(atk_type: &str, def_type: &str) -> f64 {
+    match (type_from_name(atk_type), type_from_name(def_type)) {
+        (Some(atk), Some(def)) => effectiveness(atk, def),
+        _ => 1.0,
+    }
+}
+
+/// `get_defensive_multiplier` with type names. Pass an empty `def_type2`
+/// for mono-type defenders. Unknown names are treated as neutral (1.0).
+#[wasm_bindgen]
+pub fn get_defensive_multiplier_by_name(atk_type: &str, def_type1: &str, def_type2: &str) -> f64 {
+    let Some(atk) = type_from_name(atk_type) else {
+        return 1.0;
+    };
+    let def1 = type_from_name(def_type1).map_or(1.0, |d| effectiveness(atk, d));
+    let def2 = type_from_name(def_type2).map_or(1.0, |d| effectiveness(atk, d));
+    def1 * def2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_round_trip() {
+        for (i, t) in PokemonType::ALL.iter().enumerate() {
+            assert_eq!(t.index() as usize, i);
+            assert_eq!(PokemonType::from_index(i as u8), Some(*t));
+        }
+        assert_eq!(PokemonType::from_index(18), None);
+        assert_eq!(PokemonType::from_index(255), None);
+        assert_eq!(PokemonType::from_index(-1i8 as u8), None);
+    }
+
+    #[test]
+    fn test_name_parsing_and_printing() {
+        assert_eq!("fire".parse(), Ok(PokemonType::Fire));
+        assert_eq!(" Fairy ".parse(), Ok(PokemonType::Fairy));
+        assert_eq!("ELECTRIC".parse(), Ok(PokemonType::Electric));
+        assert!("stellar".parse::<PokemonType>().is_err());
+        assert_eq!(PokemonType::Psychic.to_string(), "Psychic");
+        assert_eq!(PokemonType::Psychic.name(), "psychic");
+        for t in PokemonType::ALL {
+            assert_eq!(t.to_string().parse(), Ok(t));
+        }
+    }
+
+    #[test]
+    fn test_typed_matchups() {
+        use PokemonType::*;
+        assert_eq!(effectiveness(Fire, Grass), 2.0);
+        assert_eq!(defensive_multiplier(Ground, Fire, Some(Steel)), 4.0);
+        assert_eq!(defensive_multiplier(Ground, Flying, None), 0.0);
+        assert_eq!(TypeChart::inverse(6).matchup(Ground, Fire, Some(Steel)), 0.25);
+    }
+
+    #[test]
+    fn test_name_entry_points() {
+        assert_eq!(get_effectiveness_by_name("fire", "grass"), 2.0);
+        assert_eq!(get_effectiveness_by_name("fire", "???"), 1.0);
+        assert_eq!(get_defensive_multiplier_by_name("ground", "Fire", "Steel"), 4.0);
+        assert_eq!(get_defensive_multiplier_by_name("ground", "Fire", ""), 2.0);
+        assert_eq!(type_name(PokemonType::Dark), "dark");
+        assert_eq!(type_from_name("dark"), Some(PokemonType::Dark));
+    }
+}
@@ -27,7 +27,7 @@ const matrix: number[][] = [
   [  1,   2,   1,   1,   1,   2,  0.5,  1,  0.5,  2,   1,   2,   1,   1,   1,   1,  0.5,  1  ], // Rock
   [  0,   1,   1,   1,   1,   1,   1,   1,   1,   1,   2,   1,   1,   2,   1,  0.5,  1,   1  ], // Ghost
   [  1,   1,   1,   1,   1,   1,   1,   1,   1,   1,   1,   1,   1,   1,   2,   1,  0.5,  0  ], // Dragon
-  [  1,   1,   1,   1,   1,   1,  0.5,  1,   1,   1,   2,   1,   1,   2,   1,  0.5, 0.5, 0.5 ], // Dark
+  [  1,   1,   1,   1,   1,   1,  0.5,  1,   1,   1,   2,   1,   1,   2,   1,  0.5,  1,  0.5 ], // Dark
   [  1,  0.5, 0.5, 0.5,  1,   2,   1,   1,   1,   1,   1,   1,   2,   1,   1,   1,  0.5,  2  ], // Steel
   [  1,  0.5,  1,   1,   1,   1,   2,  0.5,  1,   1,   1,   1,   1,   1,   2,   2,  0.5,  1  ], // Fairy
 ];
@@ -57,6 +57,8 @@ export interface Pokemon {
   stats: PokemonStat[];
   types: PokemonType[];
   moves: PokemonMoveRef[];
+  /** In hectograms (0.1 kg), as returned by PokeAPI. */
+  weight?: number;
   abilities?: PokemonAbilityRef[];
   cries?: {
     latest: string;
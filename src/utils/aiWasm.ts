@@ -4,9 +4,19 @@ import { randomSeed } from "./random";
 import { typeToIndex } from "./typeChartWasm";
 import { selectAIAction as selectAIAction_JS, getBestSwitchIn as getBestSwitchIn_JS } from "./ai";
 import { getActivePokemon, getCachedMoves, getEffectiveTypes } from "./battle";
+import { getEffectiveSpeed } from "./battleHelpers";
+
+/** wasm-bindgen handle for pkmn-battle's `MoveContext`; must be freed. */
+interface WasmMoveContext {
+  free(): void;
+}
+
+/** Level every battle Pokemon's stats are calculated at. */
+const BATTLE_LEVEL = 50;
 
 let wasmModule: {
-  score_move: (power: number, move_type: number, atk_type1: number, atk_type2: number, def_type1: number, def_type2: number, accuracy: number, is_status: boolean) => number;
+  score_move_in_context: (move_name: string, power: number, move_type: number, atk_type1: number, atk_type2: number, def_type1: number, def_type2: number, accuracy: number, is_status: boolean, context: WasmMoveContext) => number;
+  MoveContext: new (atk_level: number, atk_hp: number, atk_max_hp: number, def_hp: number, def_max_hp: number, atk_speed: number, def_speed: number, atk_weight: number, def_weight: number) => WasmMoveContext;
   score_matchup: (sw_t1: number, sw_t2: number, opp_t1: number, opp_t2: number, hp_ratio: number) => number;
   select_ai_action: (move_scores: Float64Array, num_moves: number, switch_scores: Float64Array, num_switches: number, difficulty: number, seed: number, is_fainted: boolean, can_mega: boolean, can_tera: boolean, should_tera: boolean, can_dmax: boolean, should_dmax: boolean) => Float64Array;
   determine_turn_order: (p1_pri: number, p2_pri: number, p1_spd: number, p2_spd: number, seed: number) => number;
@@ -26,7 +36,8 @@ async function initWasm(): Promise<boolean> {
     const mod = await import(/* webpackIgnore: true */ "../../rust/pkmn-battle/pkg/pkmn_battle.js");
     await mod.default("/wasm/pkmn_battle_bg.wasm");
     wasmModule = {
-      score_move: mod.score_move,
+      score_move_in_context: mod.score_move_in_context,
+      MoveContext: mod.MoveContext,
       score_matchup: mod.score_matchup,
       select_ai_action: mod.select_ai_action,
       determine_turn_order: mod.determine_turn_order,
@@ -93,6 +104,18 @@ export function selectAIAction(state: BattleState): BattleTurnAction {
     const [defT1, defT2] = getTypePair(opponentActive);
 
     const cachedMoves = getCachedMoves();
+    // Battle state for fixed-damage (Seismic Toss) and variable-power (Low Kick) moves
+    const moveContext = new wasmModule.MoveContext(
+      BATTLE_LEVEL,
+      aiActive.currentHp,
+      aiActive.maxHp,
+      opponentActive.currentHp,
+      opponentActive.maxHp,
+      getEffectiveSpeed(aiActive),
+      getEffectiveSpeed(opponentActive),
+      aiActive.slot.pokemon.weight ?? 0,
+      opponentActive.slot.pokemon.weight ?? 0,
+    );
     let moveScores = new Float64Array(moves.length);
     for (let i = 0; i < moves.length; i++) {
       const moveData = cachedMoves.get(moves[i]);
@@ -101,7 +124,8 @@ export function selectAIAction(state: BattleState): BattleTurnAction {
         continue;
       }
 
-      let score = wasmModule.score_move(
+      let score = wasmModule.score_move_in_context(
+        moves[i],
         moveData.power ?? 0,
         typeToIndex(moveData.type.name),
         atkT1,
@@ -110,6 +134,7 @@ export function selectAIAction(state: BattleState): BattleTurnAction {
         defT2,
         moveData.accuracy ?? 100,
         moveData.damage_class.name === "status",
+        moveContext,
       );
 
       // Penalize moves absorbed by abilities on hard difficulty
@@ -134,6 +159,7 @@ export function selectAIAction(state: BattleState): BattleTurnAction {
 
       moveScores[i] = score;
     }
+    moveContext.free();
 
     const aliveSwitchIns = aiTeam.pokemon
       .map((p, i) => ({ pokemon: p, index: i }))